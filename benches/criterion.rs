@@ -0,0 +1,25 @@
+use criterion::{criterion_group, criterion_main};
+use rusty_check::rusty_check;
+
+rusty_check! {
+    global {
+        consts {
+            LIMIT: u64 = 100,
+        }
+    }
+
+    bench sum_of_range {
+        given {
+            numbers = (0..LIMIT).collect::<Vec<_>>(),
+        }
+        measure {
+            numbers.iter().sum::<u64>()
+        }
+        check {
+            elapsed less than 50ms
+        }
+    }
+}
+
+criterion_group!(benches, tests_criterion::sum_of_range_criterion);
+criterion_main!(benches);
@@ -0,0 +1,424 @@
+//! `cargo rusty-check`, a cargo subcommand listing the cases of `rusty_check!`
+//! invocations and `#[rusty_case]` functions in a crate, running them with `cargo test`,
+//! collecting their results into JUnit XML and JSON reports and formatting the invocations.
+
+mod scan;
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+use rusty_check_core::{
+    config_file::{discover_config, read_config_file, ConfigFileError},
+    format::format_source,
+    Config,
+};
+use rusty_check_runtime::{write_reports, CaseStatus};
+use scan::{scan_source, source_files, ListedCase};
+
+/// Records of case results collected by `report`, relative to the crate directory,
+/// unless the config files set `resultReport`.
+const DEFAULT_RESULTS: &str = "target/rusty_check/results.jsonl";
+
+const USAGE: &str = "\
+Lists and runs the cases of rusty_check! invocations and #[rusty_case] functions,
+collects their results into reports and formats rusty_check! invocations.
+
+Usage:
+    cargo rusty-check list [OPTIONS]
+    cargo rusty-check test [OPTIONS] [-- <CARGO TEST ARGS>...]
+    cargo rusty-check report [--results <PATH>] [--manifest-path <PATH>]
+    cargo rusty-check fmt [--check] [--manifest-path <PATH>]
+
+Options:
+    --format <text|json>    Output format of `list`, text by default
+    --tag <TAG>             Only cases with the tag, can be repeated
+    --unstable              Only unstable cases
+    --stable                Only cases that are not unstable
+    --results <PATH>        Result records collected by `test` and `report`,
+                            target/rusty_check/results.jsonl or resultReport of the config by default
+    --check                 Only report the files `fmt` would change, without writing them
+    --manifest-path <PATH>  Path to Cargo.toml of the crate, the current directory by default
+    -h, --help              Print this help";
+
+/// Represents the output format of `list`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Represents the subcommand to run.
+///
+/// Variants:
+/// - `List`: Prints the cases in the given format.
+/// - `Test`: Runs `cargo test` with the cases as filters, passing on the arguments,
+///   then writes the reports of the recorded results.
+/// - `Report`: Writes the JUnit XML and JSON reports of the recorded results.
+/// - `Fmt`: Formats the invocations in the sources, or only reports unformatted files if `check` is set.
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    List(Format),
+    Test(Vec<String>),
+    Report,
+    Fmt { check: bool },
+}
+
+/// Represents the parsed command line.
+#[derive(Debug, Clone, PartialEq)]
+struct Args {
+    action: Action,
+    crate_dir: PathBuf,
+    results: Option<PathBuf>,
+    tags: Vec<String>,
+    unstable: Option<bool>,
+}
+
+impl Args {
+    /// Parses the arguments following the program name.
+    ///
+    /// # Errors
+    /// Returns a message if an argument is unknown or misses its value.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+        let mut args = args.into_iter().peekable();
+        // Cargo passes the name of the subcommand as the first argument.
+        if args.peek().is_some_and(|arg| arg == "rusty-check") {
+            args.next();
+        }
+        let command = args
+            .next()
+            .ok_or("expected `list`, `test`, `report` or `fmt`")?;
+        let mut format = Format::Text;
+        let mut crate_dir = PathBuf::from(".");
+        let mut results = None;
+        let mut tags = vec![];
+        let mut unstable = None;
+        let mut cargo_args = vec![];
+        let mut check = false;
+        while let Some(arg) = args.next() {
+            let mut value =
+                |name: &str| args.next().ok_or(format!("expected a value of `{}`", name));
+            match arg.as_str() {
+                "--format" => {
+                    format = match value("--format")?.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        other => return Err(format!("unknown format `{}`", other)),
+                    }
+                }
+                "--tag" => tags.push(value("--tag")?),
+                "--unstable" => unstable = Some(true),
+                "--stable" => unstable = Some(false),
+                "--manifest-path" => {
+                    let manifest = PathBuf::from(value("--manifest-path")?);
+                    crate_dir = manifest
+                        .parent()
+                        .filter(|dir| !dir.as_os_str().is_empty())
+                        .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+                }
+                "--results" => results = Some(PathBuf::from(value("--results")?)),
+                "--check" if command == "fmt" => check = true,
+                "--" if command == "test" => {
+                    cargo_args.extend(args.by_ref());
+                }
+                other => return Err(format!("unknown argument `{}`", other)),
+            }
+        }
+        let action = match command.as_str() {
+            "list" => Action::List(format),
+            "test" => Action::Test(cargo_args),
+            "report" => Action::Report,
+            "fmt" => Action::Fmt { check },
+            other => return Err(format!("unknown command `{}`", other)),
+        };
+        Ok(Args {
+            action,
+            crate_dir,
+            results,
+            tags,
+            unstable,
+        })
+    }
+
+    /// Checks if the case passes the `--tag`, `--unstable` and `--stable` filters.
+    fn selects(&self, case: &ListedCase) -> bool {
+        (self.tags.is_empty() || self.tags.iter().any(|tag| case.tags.contains(tag)))
+            && self
+                .unstable
+                .is_none_or(|unstable| unstable == case.unstable)
+    }
+}
+
+/// Reads the config of the crate the same way the `rusty_check!` macro does.
+fn read_config(crate_dir: &Path) -> Result<Config, ConfigFileError> {
+    let (discovered, _) = discover_config(crate_dir)?;
+    match env::var("RUSTY_CONFIG") {
+        Ok(path) => Ok(read_config_file(&crate_dir.join(path))?.merge_with_other(&discovered)),
+        Err(_) => Ok(discovered),
+    }
+}
+
+/// Collects the cases of the crate, together with the errors of invocations that cannot be parsed.
+fn collect_cases(
+    crate_dir: &Path,
+    config: &Config,
+) -> Result<(Vec<ListedCase>, Vec<String>), String> {
+    let files = source_files(crate_dir).map_err(|error| {
+        format!(
+            "failed to read sources of {}: {}",
+            crate_dir.display(),
+            error
+        )
+    })?;
+    let mut cases = vec![];
+    let mut errors = vec![];
+    for file in files {
+        let source = fs::read_to_string(crate_dir.join(&file))
+            .map_err(|error| format!("failed to read {}: {}", file.display(), error))?;
+        let (file_cases, file_errors) = scan_source(&file, &source, config);
+        cases.extend(file_cases);
+        errors.extend(file_errors);
+    }
+    Ok((cases, errors))
+}
+
+/// Formats the `rusty_check!` invocations of the sources of the crate.
+///
+/// With `check`, files are only reported, and the exit code is a failure if any would change.
+fn format_files(crate_dir: &Path, check: bool) -> Result<ExitCode, String> {
+    let files = source_files(crate_dir).map_err(|error| {
+        format!(
+            "failed to read sources of {}: {}",
+            crate_dir.display(),
+            error
+        )
+    })?;
+    let mut failed = false;
+    for file in files {
+        let path = crate_dir.join(&file);
+        let source = fs::read_to_string(&path)
+            .map_err(|error| format!("failed to read {}: {}", file.display(), error))?;
+        let formatted = match format_source(&source) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                eprintln!(
+                    "error: {}:{}: {}",
+                    file.display(),
+                    error.line,
+                    error.message
+                );
+                failed = true;
+                continue;
+            }
+        };
+        if formatted == source {
+            continue;
+        }
+        if check {
+            println!("{}", file.display());
+            failed = true;
+        } else {
+            fs::write(&path, formatted)
+                .map_err(|error| format!("failed to write {}: {}", file.display(), error))?;
+        }
+    }
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Writes the JUnit XML and JSON reports of the results recorded in `records`
+/// and prints a summary of them.
+fn report_results(records: &Path) -> Result<(), String> {
+    let (junit, json, results) = write_reports(records).map_err(|error| {
+        format!(
+            "failed to collect the results in {}: {}",
+            records.display(),
+            error
+        )
+    })?;
+    let count = |status| {
+        results
+            .iter()
+            .filter(|result| result.status == status)
+            .count()
+    };
+    eprintln!(
+        "{} cases: {} passed, {} failed, {} unstable",
+        results.len(),
+        count(CaseStatus::Passed),
+        count(CaseStatus::Failed),
+        count(CaseStatus::Unstable)
+    );
+    eprintln!("Wrote {} and {}", junit.display(), json.display());
+    Ok(())
+}
+
+fn run(args: Args) -> Result<ExitCode, String> {
+    if let Action::Fmt { check } = args.action {
+        return format_files(&args.crate_dir, check);
+    }
+    let config = read_config(&args.crate_dir).map_err(|error| error.to_string())?;
+    let records = args.results.clone().unwrap_or_else(|| {
+        let path = config
+            .get_result_report()
+            .unwrap_or_else(|| DEFAULT_RESULTS.to_owned());
+        args.crate_dir.join(path)
+    });
+    if args.action == Action::Report {
+        report_results(&records)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    let (cases, errors) = collect_cases(&args.crate_dir, &config)?;
+    for error in &errors {
+        eprintln!("error: {}", error);
+    }
+    let cases: Vec<ListedCase> = cases
+        .into_iter()
+        .filter(|case| args.selects(case))
+        .collect();
+    match args.action {
+        Action::List(Format::Text) => {
+            let mut file = None;
+            for case in &cases {
+                if file != Some(&case.file) {
+                    println!("{}", case.file);
+                    file = Some(&case.file);
+                }
+                println!("    {}", case.to_text());
+            }
+        }
+        Action::List(Format::Json) => {
+            let cases: Vec<String> = cases.iter().map(ListedCase::to_json).collect();
+            println!("[{}]", cases.join(",\n"));
+        }
+        Action::Test(cargo_args) => {
+            if !errors.is_empty() {
+                return Err("the cases cannot be run until the errors are fixed".to_owned());
+            }
+            if cases.is_empty() {
+                eprintln!("No cases match the filters");
+                return Ok(ExitCode::SUCCESS);
+            }
+            // Records of earlier runs would be mixed with the results of this one.
+            if let Err(error) = fs::remove_file(&records) {
+                if error.kind() != io::ErrorKind::NotFound {
+                    return Err(format!("failed to remove {}: {}", records.display(), error));
+                }
+            }
+            let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+            let status = Command::new(cargo)
+                .arg("test")
+                .arg("--manifest-path")
+                .arg(args.crate_dir.join("Cargo.toml"))
+                .args(cargo_args)
+                .arg("--")
+                .arg("--exact")
+                .args(cases.iter().map(ListedCase::test_path))
+                .status()
+                .map_err(|error| format!("failed to run cargo test: {}", error))?;
+            // Cases record results only with the `result report` option.
+            if records.exists() {
+                report_results(&records)?;
+            }
+            return Ok(match status.code() {
+                Some(0) => ExitCode::SUCCESS,
+                Some(code) => ExitCode::from(u8::try_from(code).unwrap_or(1)),
+                None => ExitCode::FAILURE,
+            });
+        }
+        Action::Report | Action::Fmt { .. } => unreachable!("only `list` and `test` collect cases"),
+    }
+    Ok(if errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    match Args::parse(args).and_then(run) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Args, String> {
+        Args::parse(line.split_whitespace().map(str::to_owned))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args(
+            "rusty-check test --tag db --stable --manifest-path example/Cargo.toml -- --release",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            Args {
+                action: Action::Test(vec!["--release".to_owned()]),
+                crate_dir: PathBuf::from("example"),
+                results: None,
+                tags: vec!["db".to_owned()],
+                unstable: Some(false),
+            }
+        );
+        assert_eq!(
+            args("list --format json").unwrap().action,
+            Action::List(Format::Json)
+        );
+        assert_eq!(
+            args("list --format xml").unwrap_err(),
+            "unknown format `xml`"
+        );
+        assert_eq!(
+            args("list --tag").unwrap_err(),
+            "expected a value of `--tag`"
+        );
+        assert_eq!(
+            args("report --results out/results.jsonl").unwrap().results,
+            Some(PathBuf::from("out/results.jsonl"))
+        );
+        assert_eq!(
+            args("fmt --check").unwrap().action,
+            Action::Fmt { check: true }
+        );
+        assert_eq!(
+            args("list --check").unwrap_err(),
+            "unknown argument `--check`"
+        );
+        assert_eq!(args("run").unwrap_err(), "unknown command `run`");
+    }
+
+    #[test]
+    fn test_selects() {
+        let case = ListedCase {
+            file: "src/lib.rs".to_owned(),
+            module: vec!["tests".to_owned()],
+            name: "c".to_owned(),
+            tags: vec!["db".to_owned()],
+            cfg: vec![],
+            unstable: true,
+        };
+        assert!(args("list --tag db --tag slow").unwrap().selects(&case));
+        assert!(!args("list --tag slow").unwrap().selects(&case));
+        assert!(!args("list --stable").unwrap().selects(&case));
+        assert!(args("list --unstable").unwrap().selects(&case));
+    }
+}
@@ -0,0 +1,376 @@
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+use proc_macro2::TokenStream as TS;
+use quote::ToTokens;
+use rusty_check_runtime::json_string;
+use syn::{
+    visit::{self, Visit},
+    ItemFn, ItemMod, Macro, Meta,
+};
+
+use rusty_check_core::{format::render, Case, Config, RustyCase, RustyCheck};
+
+/// Directories of a crate searched for `rusty_check!` invocations.
+const SOURCE_DIRS: [&str; 4] = ["src", "tests", "examples", "benches"];
+
+/// Represents a case found in the sources of a crate.
+///
+/// A `ListedCase` consists of:
+/// - `file`: The source file, relative to the crate directory.
+/// - `module`: The module path of the generated test, without the case name.
+/// - `name`: The name of the case, which is also the name of its test.
+/// - `tags`: The tags of the case, sorted by name.
+/// - `cfg`: The `cfg` predicates of the generated module and the test.
+/// - `unstable`: Whether failed checks of the case are only reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListedCase {
+    pub file: String,
+    pub module: Vec<String>,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub cfg: Vec<String>,
+    pub unstable: bool,
+}
+
+impl ListedCase {
+    /// Returns the path of the generated test, as printed by `cargo test`.
+    pub fn test_path(&self) -> String {
+        self.module
+            .iter()
+            .chain([&self.name])
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    /// Formats the case as one line of the text listing.
+    pub fn to_text(&self) -> String {
+        let mut line = self.test_path();
+        if !self.tags.is_empty() {
+            line += &format!(" [{}]", self.tags.join(", "));
+        }
+        for cfg in &self.cfg {
+            line += &format!(" #[cfg({})]", cfg);
+        }
+        if self.unstable {
+            line += " (unstable)";
+        }
+        line
+    }
+
+    /// Formats the case as a JSON object on a single line.
+    pub fn to_json(&self) -> String {
+        let list = |items: &[String]| {
+            let items: Vec<String> = items.iter().map(|item| json_string(item)).collect();
+            format!("[{}]", items.join(","))
+        };
+        format!(
+            "{{\"file\":{},\"module\":{},\"name\":{},\"test\":{},\"tags\":{},\"cfg\":{},\"unstable\":{}}}",
+            json_string(&self.file),
+            json_string(&self.module.join("::")),
+            json_string(&self.name),
+            json_string(&self.test_path()),
+            list(&self.tags),
+            list(&self.cfg),
+            self.unstable
+        )
+    }
+}
+
+/// Returns the Rust source files of the crate in `crate_dir`, relative to it.
+///
+/// Directories with their own `Cargo.toml`, such as test fixtures, are other crates
+/// and are skipped.
+///
+/// # Errors
+/// Returns an `io::Error` if a source directory cannot be read.
+pub fn source_files(crate_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for dir in SOURCE_DIRS {
+        collect_files(crate_dir, Path::new(dir), &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn collect_files(crate_dir: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let Ok(entries) = fs::read_dir(crate_dir.join(dir)) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = dir.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            if !crate_dir.join(&path).join("Cargo.toml").is_file() {
+                collect_files(crate_dir, &path, files)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the module path of a source file within its target,
+/// assuming modules follow the file layout.
+///
+/// `src/lib.rs` and `tests/cfg.rs` are target roots, `src/db/mod.rs` is `db`
+/// and `tests/api/users.rs`, part of the `api` test, is `users`.
+pub fn file_module(relative: &Path) -> Vec<String> {
+    let mut parts: Vec<String> = relative
+        .with_extension("")
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    // Targets in `tests`, `examples`, `benches` and `src/bin` are a file or a directory.
+    let target_depth = match (parts.first(), parts.get(1)) {
+        (Some(src), Some(bin)) if src == "src" && bin == "bin" => 3,
+        (Some(src), _) if src == "src" => 1,
+        _ => 2,
+    };
+    let mut modules = parts.split_off(target_depth.min(parts.len()));
+    match modules.last().map(String::as_str) {
+        Some("mod") => _ = modules.pop(),
+        Some("lib" | "main") if modules.len() == 1 => _ = modules.pop(),
+        _ => {}
+    }
+    modules
+}
+
+/// Checks if the last segment of a path, like `rusty_check::rusty_check`, is `name`.
+fn is_named(path: &syn::Path, name: &str) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+/// Collects the cases of one source file.
+struct Scanner<'a> {
+    config: &'a Config,
+    file: String,
+    modules: Vec<String>,
+    cases: Vec<ListedCase>,
+    errors: Vec<String>,
+}
+
+impl Scanner<'_> {
+    fn error(&mut self, error: syn::Error) {
+        for error in error {
+            let start = error.span().start();
+            self.errors.push(format!(
+                "{}:{}:{}: {}",
+                self.file,
+                start.line,
+                start.column + 1,
+                error
+            ));
+        }
+    }
+
+    fn push(&mut self, module: &[String], case: &Case, module_cfg: TS) {
+        let tags: Vec<String> = case.tags().iter().map(ToString::to_string).collect();
+        let mut cfg = vec![];
+        for flags in [module_cfg, case.config().get_cfg_flags()] {
+            let flags = render(flags);
+            if !flags.is_empty() && !cfg.contains(&flags) {
+                cfg.push(flags);
+            }
+        }
+        self.cases.push(ListedCase {
+            file: self.file.clone(),
+            module: module.iter().chain(&tags).cloned().collect(),
+            name: case.ident().to_string(),
+            tags,
+            cfg,
+            unstable: case.config().get_unstable_test(),
+        });
+    }
+
+    fn scan_rusty_check(&mut self, mac: &Macro) -> syn::Result<()> {
+        let rusty = mac
+            .parse_body::<RustyCheck>()?
+            .apply_config_file(self.config);
+        let config = rusty.get_config();
+        let mut module = self.modules.clone();
+        if config.get_create_module() {
+            module.push(config.get_module_name().to_string());
+        }
+        for case in rusty.cases()? {
+            self.push(&module, &case, config.get_cfg_flags());
+        }
+        Ok(())
+    }
+
+    fn scan_rusty_case(&mut self, item: &ItemFn, index: usize) -> syn::Result<()> {
+        let args = match &item.attrs[index].meta {
+            Meta::Path(_) => TS::new(),
+            Meta::List(list) => list.tokens.clone(),
+            Meta::NameValue(meta) => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected `#[rusty_case]` or `#[rusty_case(...)]`",
+                ))
+            }
+        };
+        let mut item = item.clone();
+        item.attrs.remove(index);
+        let case = RustyCase::parse(args, item.to_token_stream())?
+            .apply_config_file(self.config)?
+            .case()
+            .clone();
+        let module = self.modules.clone();
+        self.push(&module, &case, TS::new());
+        Ok(())
+    }
+}
+
+impl<'ast> Visit<'ast> for Scanner<'_> {
+    fn visit_item_mod(&mut self, item: &'ast ItemMod) {
+        self.modules.push(item.ident.to_string());
+        visit::visit_item_mod(self, item);
+        self.modules.pop();
+    }
+
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        if let Some(index) = item
+            .attrs
+            .iter()
+            .position(|attr| is_named(attr.path(), "rusty_case"))
+        {
+            if let Err(error) = self.scan_rusty_case(item, index) {
+                self.error(error);
+            }
+        }
+        visit::visit_item_fn(self, item);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        if is_named(&mac.path, "rusty_check") {
+            if let Err(error) = self.scan_rusty_check(mac) {
+                self.error(error);
+            }
+        }
+    }
+}
+
+/// Collects the cases of a source file, given its path relative to the crate and its contents.
+///
+/// Cases get the options of `config`, read from the config files of the crate.
+/// Invocations that cannot be parsed are reported as errors prefixed with their location,
+/// other invocations of the file are still listed.
+pub fn scan_source(
+    relative: &Path,
+    source: &str,
+    config: &Config,
+) -> (Vec<ListedCase>, Vec<String>) {
+    let mut scanner = Scanner {
+        config,
+        file: relative.to_string_lossy().replace('\\', "/"),
+        modules: file_module(relative),
+        cases: vec![],
+        errors: vec![],
+    };
+    match syn::parse_file(source) {
+        Ok(file) => scanner.visit_file(&file),
+        Err(error) => scanner.error(error),
+    }
+    (scanner.cases, scanner.errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_module() {
+        let module = |path: &str| file_module(Path::new(path)).join("::");
+        assert_eq!(module("src/lib.rs"), "");
+        assert_eq!(module("src/db/mod.rs"), "db");
+        assert_eq!(module("src/db/query.rs"), "db::query");
+        assert_eq!(module("src/bin/tool.rs"), "");
+        assert_eq!(module("tests/cfg.rs"), "");
+        assert_eq!(module("tests/api/main.rs"), "");
+        assert_eq!(module("tests/api/users.rs"), "users");
+    }
+
+    #[test]
+    fn test_source_files_skip_nested_crates() {
+        let dir = std::env::temp_dir().join(format!("rusty_check_scan_{}", std::process::id()));
+        let nested = dir.join("tests/fixtures/nested");
+        fs::create_dir_all(nested.join("src")).unwrap();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "").unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        fs::write(dir.join("tests/fixtures/helpers.rs"), "").unwrap();
+        fs::write(nested.join("Cargo.toml"), "").unwrap();
+        fs::write(nested.join("src/lib.rs"), "").unwrap();
+        let files = source_files(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("tests/fixtures/helpers.rs")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_source() {
+        let source = r#"
+            use rusty_check::rusty_check;
+            mod inner {
+                rusty_check! {
+                    global { cfg { module name = checks, tags = [db], cfg = feature = "db" } }
+                    case stable { check { 1 equal 1 } }
+                    case flaky { cfg { unstable = true, tags = [slow] } check { 1 equal 1 } }
+                }
+            }
+            #[rusty_case(tags = [fast])]
+            fn plain() {
+                check! { 1 equal 1 }
+            }
+        "#;
+        let (cases, errors) = scan_source(Path::new("src/db/mod.rs"), source, &Config::new());
+        assert!(errors.is_empty(), "{:?}", errors);
+        let paths: Vec<String> = cases.iter().map(ListedCase::test_path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "db::inner::checks::db::stable",
+                "db::inner::checks::db::slow::flaky",
+                "db::fast::plain",
+            ]
+        );
+        assert_eq!(cases[1].tags, vec!["db", "slow"]);
+        assert_eq!(cases[1].cfg, vec!["feature = \"db\""]);
+        assert!(cases[1].unstable);
+        assert_eq!(
+            cases[2].to_json(),
+            "{\"file\":\"src/db/mod.rs\",\"module\":\"db::fast\",\"name\":\"plain\",\
+             \"test\":\"db::fast::plain\",\"tags\":[\"fast\"],\"cfg\":[],\"unstable\":false}"
+        );
+        assert_eq!(
+            cases[1].to_text(),
+            "db::inner::checks::db::slow::flaky [db, slow] #[cfg(feature = \"db\")] (unstable)"
+        );
+    }
+
+    #[test]
+    fn test_scan_source_reports_errors() {
+        let source = "rusty_check! { case a { check { 1 equl 1 } } }\n\
+                      rusty_check! { case b { check { 1 equal 1 } } }";
+        let (cases, errors) = scan_source(Path::new("tests/a.rs"), source, &Config::new());
+        assert_eq!(cases.len(), 1);
+        assert_eq!(
+            errors,
+            vec!["tests/a.rs:1:35: Unknown symbol `equl`, did you mean `equal`?"]
+        );
+    }
+}
@@ -86,6 +86,29 @@ rusty_check! {
             }
         }
 
+        case task_after_complete {
+            cfg {
+                timeout = 1s
+            }
+            given {
+                mut task = Task {
+                    id: 3,
+                    description: "complete".to_string(),
+                    state: TaskState::InProgress,
+                }
+            }
+            do {
+                    task.complete();
+            }
+            check {
+                    task has fields {
+                        id equal 3,
+                        description starts with "comp",
+                        state matches TaskState::Done,
+                    }
+            }
+        }
+
         case loop_over_vec_of_tasks {
             given {
                 tasks = vec![
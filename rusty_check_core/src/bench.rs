@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+
+use proc_macro2::{Group, Literal, TokenStream as TS, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{braced, parse::Parse};
+
+use super::{
+    check::Check, configure::Config, declaration_block::DeclarationBlock,
+    helpers::unknown_keyword_error, keywords as kw, runtime_env,
+};
+
+type Given = DeclarationBlock<kw::given>;
+
+/// Statistics of the measured iterations a `check` block of a bench can use:
+/// `elapsed` is the median, the others are named after what they hold.
+const STATS: &[&str] = &[
+    "elapsed", "median", "mean", "min", "max", "p90", "p95", "p99",
+];
+
+/// Represents a micro-benchmark in the RustyCheck DSL:
+///
+/// ```ignore
+/// bench sort_small {
+///     cfg { warmup = 10, iterations = 1000 }
+///     given { data = vec![3, 1, 2] }
+///     measure { let mut data = data.clone(); data.sort(); data }
+///     check { elapsed less than 5ms and p99 less than 20ms }
+/// }
+/// ```
+///
+/// A `Bench` consists of:
+/// - `ident`: The name of the bench, which is also the name of its test function.
+/// - `config`: The options of its `cfg` block, `warmup` and `iterations` set how often
+///   the `measure` block runs before and while it is measured.
+/// - `given`: An optional declaration block evaluated once before the measurements.
+/// - `measure`: The measured Rust code, its value is passed to `black_box`.
+/// - `check`: Optional conditions on the statistics, durations can be written
+///   as `5ms`, with the units `ns`, `us`, `ms` and `s`.
+#[derive(Clone, Debug)]
+pub struct Bench {
+    ident: syn::Ident,
+    config: Config,
+    given: Option<Given>,
+    measure: TS,
+    check: Option<Check>,
+}
+
+impl Bench {
+    /// Add configuration from global config into bench config
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the selected profile is not defined.
+    pub fn apply_global_config(self, global_cfg: &Config) -> syn::Result<Bench> {
+        let config = self.config.clone().apply_global(global_cfg)?;
+        Ok(Bench { config, ..self })
+    }
+
+    /// Returns the name of the bench, which is also the name of its test function.
+    pub fn ident(&self) -> &syn::Ident {
+        &self.ident
+    }
+
+    /// Returns the tags of the bench sorted by name, without duplicates.
+    pub fn tags(&self) -> Vec<syn::Ident> {
+        let mut tags = self.config.get_tags();
+        tags.sort_by_key(|tag| tag.to_string());
+        tags.dedup();
+        tags
+    }
+
+    /// Generates a function registering the measured code with Criterion,
+    /// named after the bench with a `_criterion` suffix.
+    #[cfg(feature = "criterion")]
+    pub(crate) fn criterion(&self) -> TS {
+        let name = self.ident.to_string();
+        let ident = quote::format_ident!("{}_criterion", self.ident);
+        let mut cfg_flags: TS = self.config.get_cfg_flags();
+        if !cfg_flags.is_empty() {
+            cfg_flags = quote! {#[cfg(#cfg_flags)]};
+        }
+        let given = &self.given;
+        let measure = &self.measure;
+        quote! {
+            #cfg_flags
+            pub fn #ident(__rusty_criterion: &mut ::criterion::Criterion) {
+                #given
+                __rusty_criterion.bench_function(#name, |__rusty_bencher| {
+                    __rusty_bencher.iter(|| ::std::hint::black_box({ #measure }))
+                });
+            }
+        }
+    }
+}
+
+impl Parse for Bench {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        _ = input.parse::<kw::bench>()?;
+        let ident = input.parse::<syn::Ident>()?;
+        let bench;
+        braced!(bench in input);
+        let config = if bench.peek(kw::cfg) {
+            bench.parse::<Config>()?
+        } else {
+            Config {
+                options: HashMap::new(),
+            }
+        };
+        let given = if bench.peek(kw::given) {
+            Some(bench.parse::<Given>()?)
+        } else {
+            None
+        };
+        if !bench.peek(kw::measure) {
+            return Err(unknown_keyword_error(
+                &bench,
+                "bench block",
+                kw::BENCH_BLOCKS,
+            ));
+        }
+        _ = bench.parse::<kw::measure>()?;
+        let measure;
+        braced!(measure in bench);
+        let measure = measure.parse::<TS>()?;
+        let check = if bench.is_empty() {
+            None
+        } else if bench.peek(kw::check) {
+            Some(bench.parse::<Check>()?)
+        } else {
+            return Err(unknown_keyword_error(
+                &bench,
+                "bench block",
+                kw::BENCH_BLOCKS,
+            ));
+        };
+        Ok(Bench {
+            ident,
+            config,
+            given,
+            measure,
+            check,
+        })
+    }
+}
+
+/// Replaces integer literals with a duration unit, such as `5ms`, by `Duration` values.
+fn duration_literals(tokens: TS) -> TS {
+    tokens
+        .into_iter()
+        .flat_map(|tree| match tree {
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(group.delimiter(), duration_literals(group.stream()));
+                replaced.set_span(group.span());
+                quote! {#replaced}
+            }
+            TokenTree::Literal(literal) => duration(&literal).unwrap_or_else(|| quote! {#literal}),
+            tree => quote! {#tree},
+        })
+        .collect()
+}
+
+/// Returns a `Duration` value for an integer literal with a duration unit.
+fn duration(literal: &Literal) -> Option<TS> {
+    let lit = syn::parse_str::<syn::LitInt>(&literal.to_string()).ok()?;
+    let constructor = match lit.suffix() {
+        "ns" => quote! {from_nanos},
+        "us" => quote! {from_micros},
+        "ms" => quote! {from_millis},
+        "s" => quote! {from_secs},
+        _ => return None,
+    };
+    let value = lit.base10_parse::<u64>().ok()?;
+    Some(quote! {::std::time::Duration::#constructor(#value)})
+}
+
+/// Implementation of the `ToTokens` trait for the `Bench` struct.
+///
+/// The generated test runs the `measure` block `warmup` times, then measures it
+/// `iterations` times, prints the statistics and checks the conditions on them.
+impl ToTokens for Bench {
+    fn to_tokens(&self, tokens: &mut TS) {
+        let ident = &self.ident;
+        let name = ident.to_string();
+        let given = &self.given;
+        let measure = &self.measure;
+        let mut cfg_flags: TS = self.config.get_cfg_flags();
+        if !cfg_flags.is_empty() {
+            cfg_flags = quote! {#[cfg(#cfg_flags)]};
+        }
+        let tags: Vec<String> = self.tags().iter().map(|tag| tag.to_string()).collect();
+        let tag_filter = runtime_env::tag_filter(&name, &tags);
+        let warmup = self.config.get_warmup();
+        let count = self.config.get_iterations();
+        let iterations = count as usize;
+        let stats: Vec<syn::Ident> = STATS
+            .iter()
+            .map(|stat| syn::Ident::new(stat, proc_macro2::Span::call_site()))
+            .collect();
+        let check = self.check.as_ref().map(|check| {
+            let check = check
+                .to_owned()
+                .set_options(&self.config)
+                .set_case_name(ident)
+                .without_result_record();
+            duration_literals(check.to_token_stream())
+        });
+        tokens.extend(quote! {
+            #cfg_flags
+            #[test]
+            fn #ident() {
+                #tag_filter
+                #given
+                for _ in 0..#warmup {
+                    ::std::hint::black_box({ #measure });
+                }
+                let mut __rusty_samples: ::std::vec::Vec<::std::time::Duration> =
+                    ::std::vec::Vec::with_capacity(#iterations);
+                for _ in 0..#iterations {
+                    let __rusty_start = ::std::time::Instant::now();
+                    ::std::hint::black_box({ #measure });
+                    __rusty_samples.push(__rusty_start.elapsed());
+                }
+                __rusty_samples.sort();
+                let __rusty_percentile = |__rusty_percent: usize| {
+                    __rusty_samples[((__rusty_samples.len() - 1) * __rusty_percent + 50) / 100]
+                };
+                let __rusty_total: ::std::time::Duration = __rusty_samples.iter().sum();
+                #[allow(unused_variables)]
+                let (#(#stats),*) = (
+                    __rusty_percentile(50),
+                    __rusty_percentile(50),
+                    __rusty_total / #count,
+                    __rusty_samples[0],
+                    __rusty_samples[#iterations - 1],
+                    __rusty_percentile(90),
+                    __rusty_percentile(95),
+                    __rusty_percentile(99),
+                );
+                eprintln!(
+                    "Bench `{}`: median {:?}, mean {:?}, p90 {:?}, p99 {:?}, min {:?}, max {:?} over {} iterations",
+                    #name, median, mean, p90, p99, min, max, #iterations
+                );
+                #check
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_str;
+
+    #[test]
+    fn test_parse_bench() {
+        let bench: Bench = parse_str(
+            "bench sort {
+                cfg { warmup = 2, iterations = 50 }
+                given { data = vec![3, 1, 2] }
+                measure { let mut data = data.clone(); data.sort(); data }
+                check { elapsed less than 5ms }
+            }",
+        )
+        .unwrap();
+        assert_eq!(bench.ident().to_string(), "sort");
+        assert_eq!(bench.config.get_warmup(), 2);
+        assert_eq!(bench.config.get_iterations(), 50);
+        assert!(bench.given.is_some());
+        assert!(bench.check.is_some());
+
+        let bench: Bench = parse_str("bench b { measure { 1 + 1 } }").unwrap();
+        assert!(bench.check.is_none());
+    }
+
+    #[test]
+    fn test_parse_bench_errors() {
+        let error = parse_str::<Bench>("bench b { mesure { 1 } }").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown bench block `mesure`, did you mean `measure`?"
+        );
+        let error =
+            parse_str::<Bench>("bench b { cfg { iterations = 0 } measure { 1 } }").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Expected at least one iteration, found `0`"
+        );
+    }
+
+    #[test]
+    fn test_duration_literals() {
+        let tokens = duration_literals(quote! {(elapsed < 5ms) && (p99 < 2s) && (n < 5u32)});
+        assert_eq!(
+            tokens.to_string(),
+            quote! {
+                (elapsed < ::std::time::Duration::from_millis(5u64))
+                    && (p99 < ::std::time::Duration::from_secs(2u64))
+                    && (n < 5u32)
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_to_tokens_bench() {
+        let bench: Bench = parse_str::<Bench>(
+            "bench b {
+                cfg { iterations = 10, comment = simple }
+                measure { 1 + 1 }
+                check { elapsed less than 1ms }
+            }",
+        )
+        .unwrap()
+        .apply_global_config(&Config::new())
+        .unwrap();
+        let tokens = bench.to_token_stream().to_string();
+        assert!(tokens.contains("fn b ()"));
+        assert!(tokens.contains("with_capacity (10usize)"));
+        assert!(tokens.contains("for _ in 0 .. 10u32"));
+        assert!(tokens.contains(
+            "assert ! ((elapsed < :: std :: time :: Duration :: from_millis (1u64)) , \"elapsed less than 1ms\")"
+        ));
+    }
+
+    #[test]
+    fn test_to_tokens_bench_does_not_note_results() {
+        let bench: Bench = parse_str::<Bench>(
+            "bench b {
+                cfg { result report = \"results.jsonl\", unstable = true }
+                measure { 1 + 1 }
+                check { elapsed less than 1ms }
+            }",
+        )
+        .unwrap()
+        .apply_global_config(&Config::new())
+        .unwrap();
+        let tokens = bench.to_token_stream().to_string();
+        assert!(tokens.contains("if ! ("));
+        assert!(!tokens.contains("__rusty_run"));
+    }
+
+    #[cfg(feature = "criterion")]
+    #[test]
+    fn test_criterion() {
+        let bench: Bench = parse_str("bench b { measure { 1 + 1 } }").unwrap();
+        let tokens = bench.criterion().to_string();
+        assert!(tokens
+            .contains("pub fn b_criterion (__rusty_criterion : & mut :: criterion :: Criterion)"));
+        assert!(tokens.contains("bench_function (\"b\""));
+        assert!(!bench.to_token_stream().to_string().contains("b_criterion"));
+    }
+}
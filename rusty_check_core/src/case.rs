@@ -0,0 +1,839 @@
+use std::{collections::HashMap, time::Duration};
+
+use proc_macro2::TokenStream as TS;
+use quote::{quote, ToTokens};
+use syn::{braced, parse::Parse, Token};
+
+use super::{
+    check::Check,
+    compute::Compute,
+    configure::Config,
+    declaration_block::DeclarationBlock,
+    format,
+    helpers::{escape_braces, get_ident_tokens, unknown_keyword_error},
+    keywords as kw,
+    lint::{constant_conditions, unused_given, Lint, LintLevel},
+    runtime_env, spec,
+};
+
+type Given = DeclarationBlock<kw::given>;
+
+/// A struct representing a test case in the RustyCheck DSL.
+///
+/// This struct contains the following fields:
+/// - `kw`: The keyword associated with the case.
+/// - `ident`: The identifier for the test case.
+/// - `doc`: The function documented by the case, from an optional `doc for path` clause,
+///   turning the case into a doctest, see [`Case::doc_item`].
+/// - `config`: An optional configuration for the test case.
+/// - `given`: An optional declaration block for variables used in test case.
+/// - `compute`: An optional computation block for the test case.
+/// - `check`: The check that will be performed in the test case.
+/// - `attrs`: The doc comments of a `#[rusty_case]` function, kept on the test function.
+/// - `vis`: The visibility of a `#[rusty_case]` function, kept on the test function.
+///
+/// represents grammar from this diagram:
+///
+#[derive(Clone, Debug)]
+pub struct Case {
+    ident: syn::Ident,
+    doc: Option<syn::Path>,
+    config: Config,
+    given: Option<Given>,
+    compute: Option<Compute>,
+    check: Check,
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+}
+
+impl Case {
+    /// Add configuration from global config into case config
+    ///
+    /// Settings of the selected profile override the global config,
+    /// settings of the case itself override the profile.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the selected profile is not defined.
+    pub fn apply_global_config(self, global_cfg: &Config) -> syn::Result<Case> {
+        let config = self.config.clone().apply_global(global_cfg)?;
+        Ok(Case { config, ..self })
+    }
+
+    /// Puts the doc comments and the visibility of a `#[rusty_case]` function
+    /// on the generated test function.
+    pub fn with_item_attrs(self, attrs: Vec<syn::Attribute>, vis: syn::Visibility) -> Case {
+        Case { attrs, vis, ..self }
+    }
+
+    /// Returns the name of the case, which is also the name of its test function.
+    pub fn ident(&self) -> &syn::Ident {
+        &self.ident
+    }
+
+    /// Returns the config of the case.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the tags of the case sorted by name, without duplicates.
+    pub fn tags(&self) -> Vec<syn::Ident> {
+        let mut tags = self.config.get_tags();
+        tags.sort_by_key(|tag| tag.to_string());
+        tags.dedup();
+        tags
+    }
+
+    /// Renders the case as a Markdown section of a specification document,
+    /// listing its tags, given values, actions and checks.
+    pub fn to_spec(&self) -> String {
+        let name = self.ident.to_string();
+        let mut title = name.replace('_', " ").trim().to_owned();
+        if let Some(first) = title.get(..1) {
+            title = first.to_uppercase() + &title[1..];
+        }
+        let mut spec = format!("## {} (`{}`)\n\n", title, name);
+        let tags = self.tags();
+        if !tags.is_empty() {
+            let tags: Vec<String> = tags.iter().map(|tag| format!("`{}`", tag)).collect();
+            spec += &format!("Tags: {}\n\n", tags.join(", "));
+        }
+        if self.config.get_unstable_test() {
+            spec += "Unstable: a failed check is reported without failing the test.\n\n";
+        }
+        if let Some(given) = &self.given {
+            spec += &format!("Given:\n\n{}\n", spec::declarations_spec(given));
+        }
+        if let Some(lines) = self.compute_lines() {
+            spec += &format!("When:\n\n```rust\n{}\n```\n\n", lines.join("\n"));
+        }
+        spec += &format!(
+            "Then:\n\n- {}\n",
+            format::render_str(&self.check.conditions().to_string())
+        );
+        spec
+    }
+
+    /// Returns the statements of the `do` block, rendered one per line.
+    fn compute_lines(&self) -> Option<Vec<String>> {
+        let code = self.compute.as_ref()?.rust_code().clone();
+        Some(match syn::parse2::<syn::Block>(quote! {{ #code }}) {
+            Ok(block) => block
+                .stmts
+                .iter()
+                .map(|stmt| format::render(stmt.to_token_stream()))
+                .collect(),
+            Err(_) => vec![format::render(code)],
+        })
+    }
+
+    /// Renders the case as a documentation example of the function named by its
+    /// `doc for` clause, or returns `None` if it has none.
+    ///
+    /// The example imports the parent module of the function from `crate_name`,
+    /// runs the `given` and `do` blocks in `main` and asserts the conditions of the
+    /// `check` block, with the conditions as written as the failure message.
+    /// It is laid out by `prettyplease`. Like any doctest, it only sees the public
+    /// items of the crate.
+    pub fn to_doc_example(&self, crate_name: &str) -> Option<String> {
+        let path = self.doc.as_ref()?;
+        let mut segments: Vec<&syn::Ident> =
+            path.segments.iter().map(|segment| &segment.ident).collect();
+        if segments[0] == "crate" {
+            segments.remove(0);
+        }
+        let krate = syn::Ident::new(crate_name, proc_macro2::Span::call_site());
+        let parents = &segments[..segments.len() - 1];
+        let given = self.given.iter().flat_map(|given| given.statements());
+        let compute = self.compute.as_ref().map(Compute::rust_code);
+        let conditions = self.check.conditions();
+        let slots = conditions.unordered_slots();
+        let message = escape_braces(&format::render_str(&conditions.to_string()));
+        // Code that is not valid Rust fails to compile in the test of the case already.
+        let assertion = match syn::parse2::<syn::Expr>(conditions.to_token_stream()).ok()? {
+            syn::Expr::Paren(paren) => *paren.expr,
+            assertion => assertion,
+        };
+        let example = syn::parse2::<syn::File>(quote! {
+            use #krate #(:: #parents)* ::*;
+
+            fn main() {
+                #(#given)*
+                #compute
+                #slots
+                assert!(#assertion, #message);
+            }
+        })
+        .ok()?;
+        let name = segments
+            .iter()
+            .map(|segment| segment.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        Some(format!(
+            "Example of [`crate::{}`], generated from the case `{}`:\n\n```\n{}```\n",
+            name,
+            self.ident,
+            prettyplease::unparse(&example)
+        ))
+    }
+
+    /// Generates a hidden item documented with the example of [`Case::to_doc_example`],
+    /// only compiled by `rustdoc` so the example runs as a doctest.
+    ///
+    /// The example is not part of the documentation of the function, which cannot be
+    /// extended from another item. It only runs for invocations in the library of a
+    /// crate, as `rustdoc` does not collect doctests of integration tests.
+    ///
+    /// `module_cfg` holds the `cfg` predicates of the generated test module.
+    pub fn doc_item(&self, crate_name: &str, ident: &syn::Ident, module_cfg: TS) -> TS {
+        let Some(example) = self.to_doc_example(crate_name) else {
+            return TS::new();
+        };
+        let flags = [module_cfg, self.config.get_cfg_flags()]
+            .into_iter()
+            .filter(|flags| !flags.is_empty());
+        quote! {
+            #[cfg(all(doctest, #(#flags),*))]
+            #[doc = #example]
+            #[allow(dead_code, non_camel_case_types)]
+            struct #ident {}
+        }
+    }
+
+    /// Collects lint diagnostics of the case:
+    /// - `given` identifiers never used in later `given` values, `do` or `check`.
+    /// - Conditions whose result does not depend on the tested code.
+    pub fn lints(&self) -> Vec<Lint> {
+        let mut used = vec![];
+        if let Some(given) = &self.given {
+            for value in given.values() {
+                used.extend(get_ident_tokens(&value.to_token_stream()));
+            }
+        }
+        if let Some(compute) = &self.compute {
+            used.extend(get_ident_tokens(compute.rust_code()));
+        }
+        used.extend(get_ident_tokens(&self.check.conditions().to_token_stream()));
+        let mut lints = self
+            .given
+            .as_ref()
+            .map(|given| unused_given(&given.idents(), &used))
+            .unwrap_or_default();
+        lints.extend(constant_conditions(self.check.conditions()));
+        lints
+    }
+}
+/// Implementation of the `Parse` trait for the `Case` struct.
+///
+/// This implementation allows parsing a `Case` from a token stream in the RustyCheck DSL.
+/// It handles parsing the `case` keyword, identifier, and optional blocks for configuration,
+/// variable declarations, computations, and checks.
+impl Parse for Case {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        _ = input.parse::<kw::case>()?;
+        let ident = input.parse::<syn::Ident>()?;
+        let case;
+        braced!(case in input);
+        let doc = if case.peek(kw::doc) {
+            _ = case.parse::<kw::doc>()?;
+            _ = case.parse::<Token![for]>()?;
+            let path = syn::Path::parse_mod_style(&case)?;
+            let first = &path.segments[0].ident;
+            if path.leading_colon.is_some() || first == "self" || first == "super" {
+                return Err(syn::Error::new_spanned(
+                    &path,
+                    "Expected a path from the crate root, such as `crate::math::add`",
+                ));
+            }
+            if path.segments.len() == 1 && first == "crate" {
+                return Err(syn::Error::new_spanned(
+                    &path,
+                    "Expected a path to a function, such as `crate::math::add`",
+                ));
+            }
+            Some(path)
+        } else {
+            None
+        };
+        let config = if case.peek(kw::cfg) {
+            case.parse::<Config>()?
+        } else {
+            Config {
+                options: HashMap::new(),
+            }
+        };
+        let given = if case.peek(kw::given) {
+            Some(case.parse::<Given>()?)
+        } else {
+            None
+        };
+        let compute = if case.peek(Token![do]) {
+            Some(case.parse::<Compute>()?)
+        } else {
+            None
+        };
+        if !case.peek(kw::check) {
+            return Err(unknown_keyword_error(&case, "case block", kw::CASE_BLOCKS));
+        }
+        let check = case.parse::<Check>()?;
+        Ok(Case {
+            ident,
+            doc,
+            config,
+            given,
+            compute,
+            check,
+            attrs: vec![],
+            vis: syn::Visibility::Inherited,
+        })
+    }
+}
+
+/// Wraps the body of a test so it is run again when it fails.
+///
+/// The body runs up to `retries + 1` times, waiting `backoff` after the first failed
+/// attempt and twice as long after every next one. The test passes on the first
+/// successful attempt and reports how many attempts it needed.
+///
+/// With `min_pass_rate` set, all attempts are run and the test fails if
+/// the share of successful attempts is below the rate.
+///
+/// The body is wrapped by [`with_timeout`] first, so a timeout applies to every attempt
+/// and an attempt that times out is retried like any other failure.
+fn with_retries(
+    ident: &syn::Ident,
+    retries: u32,
+    backoff: Option<Duration>,
+    min_pass_rate: Option<f64>,
+    body: TS,
+) -> TS {
+    let name = ident.to_string();
+    let attempts = retries.saturating_add(1);
+    let wait = backoff.map(|backoff| {
+        let millis = backoff.as_millis() as u64;
+        quote! {
+            ::std::thread::sleep(
+                ::std::time::Duration::from_millis(#millis)
+                    .saturating_mul(2u32.saturating_pow(__rusty_attempt - 1)),
+            );
+        }
+    });
+    let attempt = quote! {
+        ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+            #body
+        }))
+    };
+    match min_pass_rate {
+        None => quote! {
+            let mut __rusty_attempt: u32 = 1;
+            loop {
+                match #attempt {
+                    Ok(()) => {
+                        if __rusty_attempt > 1 {
+                            eprintln!("Case `{}` passed after {} attempts", #name, __rusty_attempt);
+                        }
+                        break;
+                    }
+                    Err(__rusty_panic) if __rusty_attempt >= #attempts => {
+                        eprintln!("Case `{}` failed all {} attempts", #name, __rusty_attempt);
+                        ::std::panic::resume_unwind(__rusty_panic);
+                    }
+                    Err(_) => {
+                        #wait
+                        __rusty_attempt += 1;
+                    }
+                }
+            }
+        },
+        Some(rate) => quote! {
+            let mut __rusty_passed: u32 = 0;
+            for __rusty_attempt in 1..=#attempts {
+                if #attempt.is_ok() {
+                    __rusty_passed += 1;
+                }
+                if __rusty_attempt < #attempts {
+                    #wait
+                }
+            }
+            let __rusty_pass_rate = f64::from(__rusty_passed) / f64::from(#attempts);
+            if __rusty_pass_rate < #rate {
+                panic!(
+                    "Case `{}` passed {} of {} attempts, the pass rate {:.2} is below {}",
+                    #name, __rusty_passed, #attempts, __rusty_pass_rate, #rate
+                );
+            }
+            eprintln!("Case `{}` passed {} of {} attempts", #name, __rusty_passed, #attempts);
+        },
+    }
+}
+
+/// Wraps the body of a test so it runs on a worker thread.
+///
+/// The test fails if the worker does not finish within `timeout`, naming the case and
+/// the elapsed time. A panic of the worker, like a failed check, is passed on to the test.
+///
+/// A thread cannot be stopped from outside, so a worker that times out is left running
+/// until it finishes or the test binary exits. The whole body, with its `given` bindings,
+/// moves to the worker, so anything it captures must be `Send` and `'static`.
+fn with_timeout(ident: &syn::Ident, timeout: Duration, body: TS) -> TS {
+    let name = ident.to_string();
+    let millis = timeout.as_millis() as u64;
+    quote! {
+        let __rusty_timeout = ::std::time::Duration::from_millis(#millis);
+        let __rusty_start = ::std::time::Instant::now();
+        let (__rusty_sender, __rusty_receiver) = ::std::sync::mpsc::channel::<()>();
+        let __rusty_worker = ::std::thread::spawn(move || {
+            #body
+            let _ = __rusty_sender.send(());
+        });
+        match __rusty_receiver.recv_timeout(__rusty_timeout) {
+            Ok(()) => {}
+            Err(::std::sync::mpsc::RecvTimeoutError::Timeout) => panic!(
+                "Case `{}` timed out after {:?}, the timeout is {:?}",
+                #name,
+                __rusty_start.elapsed(),
+                __rusty_timeout
+            ),
+            Err(::std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                if let Err(__rusty_panic) = __rusty_worker.join() {
+                    ::std::panic::resume_unwind(__rusty_panic);
+                }
+            }
+        }
+    }
+}
+
+/// Wraps the body of a test so its result is appended to the report file at `path`.
+///
+/// The record holds the time the body ran and whether it passed, failed, or failed
+/// an unstable check. A panic of the body, like a failed check, is passed on to the test.
+/// Relative paths are resolved against the directory of the tested crate.
+fn with_result_record(ident: &syn::Ident, path: &str, body: TS) -> TS {
+    let name = ident.to_string();
+    quote! {
+        let __rusty_run = ::rusty_check_runtime::next_run_id();
+        let __rusty_start = ::std::time::Instant::now();
+        let __rusty_outcome = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+            #body
+        }));
+        if let Err(__rusty_error) = ::rusty_check_runtime::record_case_result(
+            ::std::path::Path::new(::std::env!("CARGO_MANIFEST_DIR")).join(#path),
+            __rusty_run,
+            #name,
+            ::std::module_path!(),
+            __rusty_start.elapsed(),
+            __rusty_outcome.as_ref().err().map(|__rusty_panic| &**__rusty_panic),
+        ) {
+            eprintln!("Failed to record the result of case `{}` in {}: {}", #name, #path, __rusty_error);
+        }
+        if let Err(__rusty_panic) = __rusty_outcome {
+            ::std::panic::resume_unwind(__rusty_panic);
+        }
+    }
+}
+
+/// Implementation of the `ToTokens` trait for the `Case` struct.
+///
+/// This implementation converts a `Case` into a token stream that represents a test function
+/// in Rust. It includes optional configuration attributes, variable declarations, computations,
+/// and the final check.
+impl ToTokens for Case {
+    fn to_tokens(&self, tokens: &mut TS) {
+        let ident = &self.ident;
+        let attrs = &self.attrs;
+        let vis = &self.vis;
+        let given = &self.given;
+        let compute = &self.compute;
+        let mut cfg_flags: TS = self.config.get_cfg_flags();
+        if !cfg_flags.is_empty() {
+            cfg_flags = quote! {#[cfg(#cfg_flags)]};
+        }
+        let check = self
+            .check
+            .to_owned()
+            .set_options(&self.config)
+            .set_case_name(ident);
+        let lints = self.lints();
+        let (warnings, errors): (Vec<TS>, Vec<TS>) = match self.config.get_lint_level() {
+            LintLevel::Allow => (vec![], vec![]),
+            LintLevel::Warn => (lints.iter().map(Lint::to_warning).collect(), vec![]),
+            LintLevel::Deny => (vec![], lints.iter().map(Lint::to_error).collect()),
+        };
+        let tags: Vec<String> = self.tags().iter().map(|tag| tag.to_string()).collect();
+        let tag_filter = runtime_env::tag_filter(&ident.to_string(), &tags);
+        let mut body = quote! {
+            #(#warnings)*
+            #given
+            #compute
+            #check
+        };
+        if let Some(timeout) = self.config.get_timeout() {
+            body = with_timeout(ident, timeout, body);
+        }
+        let retries = self.config.get_retries();
+        let min_pass_rate = self.config.get_min_pass_rate();
+        if retries > 0 || min_pass_rate.is_some() {
+            let backoff = self.config.get_backoff();
+            body = with_retries(ident, retries, backoff, min_pass_rate, body);
+        }
+        if let Some(path) = self.config.get_result_report() {
+            body = with_result_record(ident, &path, body);
+        }
+        tokens.extend(quote! {
+            #(#errors)*
+            #(#attrs)*
+            #cfg_flags
+            #[test]
+            #vis fn #ident() {
+                #tag_filter
+                #body
+            }
+        });
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::configure::{CommentType, ConfigOption, ConfigOptionName};
+    use quote::ToTokens;
+    use syn::{parse_quote, parse_str, Ident};
+
+    fn lint_messages(case: &str) -> Vec<String> {
+        parse_str::<Case>(case)
+            .unwrap()
+            .lints()
+            .iter()
+            .map(|lint| lint.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_case() {
+        let input: proc_macro2::TokenStream = parse_quote! {
+            case my_test {
+                given {
+                    x = 10
+                }
+                do {
+                    let y = x + 5;
+                }
+                check { x equal y }
+            }
+        };
+
+        let parsed_case: Case = syn::parse2(input).unwrap();
+
+        assert_eq!(parsed_case.ident.to_string(), "my_test");
+        assert!(parsed_case.given.is_some());
+        assert!(parsed_case.compute.is_some());
+        assert!(matches!(parsed_case.check, Check { .. }));
+    }
+
+    #[test]
+    fn test_lints_unused_given() {
+        let messages = lint_messages(
+            "case c { given { x = 1, y = x, z = 3, w = 4 } do { let v = z; } check { y.w equal v } }",
+        );
+        assert_eq!(
+            messages,
+            vec!["`w` is given but never used in `do` or `check`"]
+        );
+    }
+
+    #[test]
+    fn test_lints_constant_conditions() {
+        let messages = lint_messages("case c { check { 1 equal 2 or x equal x } }");
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn test_lint_level_deny_emits_errors() {
+        let case: Case =
+            parse_str("case c { cfg { lint = deny } given { x = 1 } check { 1 equal 1 } }")
+                .unwrap();
+        let tokens = case.to_token_stream().to_string();
+        assert_eq!(tokens.matches("compile_error").count(), 2);
+
+        let case: Case = parse_str("case c { given { x = 1 } check { 1 equal 1 } }").unwrap();
+        assert!(!case.to_token_stream().to_string().contains("compile_error"));
+    }
+
+    fn config_with_profiles() -> Config {
+        let mut global: Config = parse_str("cfg { comment = simple, unstable = false }").unwrap();
+        let profile: Config =
+            parse_str("cfg { unstable = true, comment = show values, lint = warn }").unwrap();
+        global.options.insert(
+            ConfigOptionName::Profiles,
+            ConfigOption::Profiles {
+                profiles: HashMap::from([("integration".to_owned(), profile)]),
+            },
+        );
+        global
+    }
+
+    #[test]
+    fn test_apply_global_config_with_profile() {
+        let case: Case = parse_str(
+            "case c { cfg { profile = integration, comment = simple } check { 1 equal 1 } }",
+        )
+        .unwrap();
+        let case = case.apply_global_config(&config_with_profiles()).unwrap();
+        assert!(case.config.get_unstable_test());
+        assert_eq!(case.config.get_lint_level(), LintLevel::Warn);
+        assert_eq!(case.config.get_comment_type(), CommentType::Simple);
+    }
+
+    #[test]
+    fn test_apply_global_config_merges_tags() {
+        let case: Case =
+            parse_str("case c { cfg { tags = [slow, db] } check { 1 equal 1 } }").unwrap();
+        let global: Config = parse_str("cfg { tags = [network, db] }").unwrap();
+        let case = case.apply_global_config(&global).unwrap();
+        let tags: Vec<String> = case.tags().iter().map(|tag| tag.to_string()).collect();
+        assert_eq!(tags, vec!["db", "network", "slow"]);
+    }
+
+    #[test]
+    fn test_apply_global_config_unknown_profile() {
+        let case: Case =
+            parse_str("case c { cfg { profile = integraton } check { 1 equal 1 } }").unwrap();
+        let error = case
+            .apply_global_config(&config_with_profiles())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown profile `integraton`, did you mean `integration`?"
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        let config: Config = parse_str("cfg { timeout = 500ms }").unwrap();
+        assert_eq!(config.get_timeout(), Some(Duration::from_millis(500)));
+        let config: Config = parse_str("cfg { timeout = 2s }").unwrap();
+        assert_eq!(config.get_timeout(), Some(Duration::from_secs(2)));
+        assert_eq!(
+            parse_str::<Config>("cfg { timeout = 2min }")
+                .unwrap_err()
+                .to_string(),
+            "Expected a duration such as `2ms` or `2s`, found `2min`"
+        );
+
+        let case: Case =
+            parse_str("case c { cfg { timeout = 500ms } check { 1 equal 1 } }").unwrap();
+        let tokens = case.to_token_stream().to_string();
+        assert!(tokens.contains("recv_timeout"));
+        assert!(tokens.contains("from_millis (500u64)"));
+    }
+
+    #[test]
+    fn test_retries() {
+        let config: Config =
+            parse_str("cfg { retries = 3, backoff = 10ms, min pass rate = 0.75 }").unwrap();
+        assert_eq!(config.get_retries(), 3);
+        assert_eq!(config.get_backoff(), Some(Duration::from_millis(10)));
+        assert_eq!(config.get_min_pass_rate(), Some(0.75));
+        assert_eq!(
+            parse_str::<Config>("cfg { min pass rate = 2.0 }")
+                .unwrap_err()
+                .to_string(),
+            "Expected a pass rate between 0.0 and 1.0, found `2.0`"
+        );
+
+        let case: Case = parse_str("case c { cfg { retries = 2 } check { 1 equal 1 } }").unwrap();
+        let tokens = case.to_token_stream().to_string();
+        assert!(tokens.contains("catch_unwind"));
+        assert!(tokens.contains("__rusty_attempt >= 3u32"));
+        assert!(!tokens.contains("sleep"));
+
+        let case: Case = parse_str(
+            "case c { cfg { retries = 2, backoff = 10ms, timeout = 1s } check { 1 equal 1 } }",
+        )
+        .unwrap();
+        let tokens = case.to_token_stream().to_string();
+        let attempt = tokens.find("catch_unwind").unwrap();
+        assert!(attempt < tokens.find("recv_timeout").unwrap());
+    }
+
+    #[test]
+    fn test_result_report() {
+        let case: Case = parse_str(
+            "case c { cfg { result report = \"results.jsonl\", timeout = 1s } check { 1 equal 1 } }",
+        )
+        .unwrap();
+        let tokens = case.to_token_stream().to_string();
+        let record = tokens.find("record_case_result").unwrap();
+        assert!(tokens.find("recv_timeout").unwrap() < record);
+        assert!(tokens.contains("join (\"results.jsonl\")"));
+        assert!(tokens.contains("resume_unwind"));
+    }
+
+    #[test]
+    fn test_to_spec() {
+        let case: Case = parse_str(
+            "case task_after_run {
+                cfg { tags = [slow], unstable = true }
+                given { mut task = Task::new(2) }
+                do { task.run(); let state = task.state; }
+                check { for each t in &[state], t equal State::InProgress }
+            }",
+        )
+        .unwrap();
+        assert_eq!(
+            case.to_spec(),
+            "## Task after run (`task_after_run`)
+
+Tags: `slow`
+
+Unstable: a failed check is reported without failing the test.
+
+Given:
+
+- `task` is `Task::new(2)`
+
+When:
+
+```rust
+task.run();
+let state = task.state;
+```
+
+Then:
+
+- for each t in &[state], t equal State::InProgress
+"
+        );
+    }
+
+    #[test]
+    fn test_to_doc_example() {
+        let case: Case = parse_str(
+            "case addition {
+                doc for crate::math::add
+                given { a = 2, mut b = 3 }
+                do { b += 1; let sum = add(a, b); }
+                check { sum equal 6 }
+            }",
+        )
+        .unwrap();
+        assert_eq!(
+            case.to_doc_example("calc").unwrap(),
+            "Example of [`crate::math::add`], generated from the case `addition`:\n\n\
+             ```\n\
+             use calc::math::*;\n\
+             fn main() {\n\
+             \x20   let a = 2;\n\
+             \x20   let mut b = 3;\n\
+             \x20   b += 1;\n\
+             \x20   let sum = add(a, b);\n\
+             \x20   assert!(sum == 6, \"sum equal 6\");\n\
+             }\n\
+             ```\n"
+        );
+
+        let case: Case = parse_str("case c { doc for add check { add(1, 1) equal 2 } }").unwrap();
+        assert!(case.to_doc_example("calc").unwrap().contains(
+            "use calc::*;\nfn main() {\n    assert!(add(1, 1) == 2, \"add(1, 1) equal 2\");"
+        ));
+
+        let case: Case = parse_str("case c { check { 1 equal 1 } }").unwrap();
+        assert!(case.to_doc_example("calc").is_none());
+        assert!(case
+            .doc_item("calc", &parse_quote! { doc }, TS::new())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_doc_example_round_trip() {
+        let case: Case = parse_str(
+            "case c {
+                doc for crate::items::Item
+                given { items = vec![Item { id: 1 }], ids = vec![1] }
+                check {
+                    for each item in &items, item has fields { id greater than 0 }
+                    and items.iter().map(|item| item.id) same elements as ids
+                }
+            }",
+        )
+        .unwrap();
+        let example = case.to_doc_example("calc").unwrap();
+        let code = example
+            .split_once("```\n")
+            .and_then(|(_, rest)| rest.strip_suffix("```\n"))
+            .unwrap();
+        let file = syn::parse_file(code).unwrap();
+        assert_eq!(prettyplease::unparse(&file), code);
+        assert!(code.contains("::std::cell::OnceCell::new()"));
+        assert!(code.contains("\"for each item in &items, item has fields {{ id greater than 0 }}"));
+    }
+
+    #[test]
+    fn test_doc_item() {
+        let case: Case = parse_str::<Case>(
+            "case c { doc for math::add cfg { cfg = feature = \"math\" } check { 1 equal 1 } }",
+        )
+        .unwrap()
+        .apply_global_config(&Config::new())
+        .unwrap();
+        let tokens = case
+            .doc_item(
+                "calc",
+                &parse_quote! { __rusty_doc_tests_c },
+                quote! { unix },
+            )
+            .to_string();
+        assert!(tokens.starts_with(
+            "# [cfg (all (doctest , unix , feature = \"math\"))] # [doc = \"Example of [`crate::math::add`]"
+        ));
+        assert!(tokens.ends_with("struct __rusty_doc_tests_c { }"));
+    }
+
+    #[test]
+    fn test_parse_doc_errors() {
+        let error =
+            parse_str::<Case>("case c { doc for super::add check { 1 equal 1 } }").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Expected a path from the crate root, such as `crate::math::add`"
+        );
+        let error = parse_str::<Case>("case c { doc for crate check { 1 equal 1 } }").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Expected a path to a function, such as `crate::math::add`"
+        );
+        let error = parse_str::<Case>("case c { dc for add check { 1 equal 1 } }").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown case block `dc`, did you mean `do`?"
+        );
+    }
+
+    #[test]
+    fn test_to_tokens_case() {
+        let mut tokens = proc_macro2::TokenStream::new();
+        let ident: Ident = parse_quote! { my_test_case };
+        let given = Some(parse_str("given {x = 20}").unwrap());
+        let check = parse_str("check { x equal 20 }").unwrap();
+        let case = Case {
+            ident,
+            doc: None,
+            config: Config::default(),
+            given,
+            compute: None,
+            check,
+            attrs: vec![],
+            vis: syn::Visibility::Inherited,
+        };
+
+        let check = case.check.to_token_stream();
+        let tag_filter = runtime_env::tag_filter("my_test_case", &[]);
+
+        case.to_tokens(&mut tokens);
+
+        assert_eq!(
+            tokens.to_string(),
+            quote! { #[test] fn my_test_case() { #tag_filter let x = 20; #check } }.to_string()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use super::{
+    conditions::Conditions,
+    configure::{CommentType, Config},
+    helpers::{Comment, ToComment},
+    keywords as kw, runtime_env,
+};
+use proc_macro2::TokenStream as TS;
+use quote::{quote, ToTokens};
+use syn::{braced, parse::Parse, Ident};
+
+/// Represents a `Check` block in the RustyCheck DSL.
+///
+/// A `Check` block is used to define assertions in the DSL. It contains:
+/// - A `keyword`: The `check` keyword.
+/// - `conditions`: The conditions to be asserted.
+/// - `comment`: A string representation of the conditions for debugging or documentation purposes.
+///
+/// represents grammar from this diagram:
+///
+#[derive(Clone, Debug)]
+pub struct Check {
+    conditions: Conditions,
+    comment_type: CommentType,
+    test_unstable: bool,
+    unstable_report: Option<String>,
+    record_results: bool,
+    case_name: String,
+}
+
+impl Check {
+    /// Creates a `Check` of `conditions` with the default options.
+    pub fn new(conditions: Conditions) -> Check {
+        Check {
+            conditions,
+            comment_type: CommentType::default(),
+            test_unstable: false,
+            unstable_report: None,
+            record_results: false,
+            case_name: String::new(),
+        }
+    }
+    /// Returns the conditions of the check.
+    pub fn conditions(&self) -> &Conditions {
+        &self.conditions
+    }
+    /// Read Check options from config
+    pub fn set_options(self, config: &Config) -> Check {
+        Check {
+            comment_type: config.get_comment_type(),
+            test_unstable: config.get_unstable_test(),
+            unstable_report: config.get_unstable_report(),
+            record_results: config.get_result_report().is_some(),
+            ..self
+        }
+    }
+    /// Sets the name of the case the check belongs to, used in unstable and result reports.
+    pub fn set_case_name(self, ident: &Ident) -> Check {
+        Check {
+            case_name: ident.to_string(),
+            ..self
+        }
+    }
+    /// Stops noting failed unstable checks for the result report, for checks that are
+    /// not wrapped in a case declaring the identifier of the run.
+    pub(crate) fn without_result_record(self) -> Check {
+        Check {
+            record_results: false,
+            ..self
+        }
+    }
+    /// Generates a call of an assertion macro, such as `assert` or `debug_assert`,
+    /// with the conditions and the comment, as used by the standalone `check!` macros.
+    ///
+    /// Unlike [`Check::to_tokens`] it reads no environment variables,
+    /// so it can be used outside of tests.
+    pub fn to_assertion(&self, assertion: Ident) -> TS {
+        let conditions = &self.conditions;
+        let comment = self.conditions.to_comment(self.comment_type);
+        let slots = self.conditions.unordered_slots();
+        if slots.is_empty() {
+            quote! {#assertion!(#conditions, #comment)}
+        } else {
+            quote! {{
+                #slots
+                #assertion!(#conditions, #comment)
+            }}
+        }
+    }
+    /// Generates a statement appending a failed unstable check to the report file, if set.
+    ///
+    /// Relative paths are resolved against the directory of the tested crate.
+    fn record_failure(&self) -> Option<TS> {
+        let path = self.unstable_report.as_ref()?;
+        let case_name = &self.case_name;
+        Some(quote! {
+            if let Err(__rusty_error) = ::rusty_check_runtime::record_unstable_failure(
+                ::std::path::Path::new(::std::env!("CARGO_MANIFEST_DIR")).join(#path),
+                #case_name,
+                ::std::module_path!(),
+                &__rusty_comment,
+            ) {
+                eprintln!("Failed to record unstable test failure in {}: {}", #path, __rusty_error);
+            }
+        })
+    }
+    /// Generates a statement noting a failed unstable check, so the result of the case
+    /// is recorded as unstable, if results are recorded.
+    ///
+    /// The identifier of the run, `__rusty_run`, is declared by the case around the check.
+    fn note_failure(&self) -> Option<TS> {
+        if !self.record_results {
+            return None;
+        }
+        Some(quote! {
+            ::rusty_check_runtime::note_unstable_failure(__rusty_run, &__rusty_comment);
+        })
+    }
+}
+
+impl Parse for Check {
+    /// Parses a `Check` block from the input stream.
+    ///
+    /// # Parameters
+    /// - `input`: The parse stream to read from.
+    ///
+    /// # Returns
+    /// A parsed `Check` instance containing the keyword, conditions, and a comment.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the input cannot be parsed as a valid `Check` block.
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        _ = input.parse::<kw::check>()?;
+        let conditions;
+        braced!(conditions in input);
+        let conditions = conditions.parse::<Conditions>()?;
+        Ok(Check::new(conditions))
+    }
+}
+
+impl ToTokens for Check {
+    /// Converts the `Check` block into tokens for code generation.
+    ///
+    /// This implementation generates a statement panicking with a comment for debugging
+    /// purposes if the conditions do not hold, the conditions are evaluated once.
+    /// Unstable checks only print the comment, unless `RUSTY_CHECK_UNSTABLE=fail` is set
+    /// when the test runs. With `RUSTY_CHECK_COMMENT=simple` the comment omits the values.
+    ///
+    /// # Parameters
+    /// - `tokens`: The token stream to append the generated code to.
+    fn to_tokens(&self, tokens: &mut TS) {
+        let conditions = &self.conditions;
+        let comment = self.conditions.to_comment(self.comment_type);
+        let simple_comment = self.conditions.to_comment(CommentType::Simple);
+        tokens.extend(self.conditions.unordered_slots());
+        // The conditions are emitted once, only the message is chosen at runtime.
+        let choose = |report: &dyn Fn(&Comment) -> TS| {
+            if self.comment_type == CommentType::Simple {
+                report(&comment)
+            } else {
+                let simple = runtime_env::simple_comment();
+                let (report, simple_report) = (report(&comment), report(&simple_comment));
+                quote! {
+                    if #simple {
+                        #simple_report
+                    } else {
+                        #report
+                    }
+                }
+            }
+        };
+        if self.test_unstable {
+            let unstable_fails = runtime_env::unstable_fails();
+            let message = choose(&|comment| quote! {::std::format!(#comment)});
+            let record = self.record_failure();
+            let note = self.note_failure();
+            tokens.extend(quote! {
+                if !#conditions {
+                    let __rusty_comment = #message;
+                    if #unstable_fails {
+                        panic!("{}", __rusty_comment);
+                    } else {
+                        eprintln!("Unstable test failed, {}", __rusty_comment);
+                        #record
+                        #note
+                    }
+                }
+            });
+        } else if self.comment_type == CommentType::Simple {
+            tokens.extend(quote! {assert!(#conditions,#comment);});
+        } else {
+            let report = choose(&|comment| quote! {panic!(#comment);});
+            tokens.extend(quote! {
+                if !#conditions {
+                    #report
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use super::*;
+    #[test]
+    fn test_check() {
+        let check = parse_str::<Check>("check { a equal 10 }")
+            .unwrap()
+            .to_token_stream()
+            .to_string();
+        let result = parse_str::<TS>(
+            "if !(a==10) {
+                if ::std::env::var(\"RUSTY_CHECK_COMMENT\").is_ok_and(|__rusty_value| __rusty_value.trim() == \"simple\") {
+                    panic!(\"a equal 10\");
+                } else {
+                    panic!(\"a equal 10 where, a={:?}\" ,a );
+                }
+            }",
+        )
+        .unwrap()
+        .to_string();
+        assert_eq!(check, result);
+    }
+
+    #[test]
+    fn test_check_simple() {
+        let check = parse_str::<Check>("check { a equal 10 }")
+            .unwrap()
+            .set_options(&parse_str::<Config>("cfg { comment = simple }").unwrap())
+            .to_token_stream()
+            .to_string();
+        let result = parse_str::<TS>("assert!((a==10),\"a equal 10\");")
+            .unwrap()
+            .to_string();
+        assert_eq!(check, result);
+    }
+
+    #[test]
+    fn test_check_unstable() {
+        let config = parse_str::<Config>("cfg { comment = simple, unstable = true }").unwrap();
+        let check = parse_str::<Check>("check { a equal 10 }")
+            .unwrap()
+            .set_options(&config)
+            .to_token_stream()
+            .to_string();
+        let result = parse_str::<TS>(
+            "if !(a==10) {
+                let __rusty_comment = ::std::format!(\"a equal 10\");
+                if ::std::env::var(\"RUSTY_CHECK_UNSTABLE\").is_ok_and(|__rusty_value| __rusty_value.trim() == \"fail\") {
+                    panic!(\"{}\", __rusty_comment);
+                } else {
+                    eprintln!(\"Unstable test failed, {}\", __rusty_comment);
+                }
+            }",
+        )
+        .unwrap()
+        .to_string();
+        assert_eq!(check, result);
+    }
+
+    #[test]
+    fn test_check_unstable_report() {
+        let config = parse_str::<Config>(
+            "cfg { comment = simple, unstable = true, unstable report = \"unstable.jsonl\" }",
+        )
+        .unwrap();
+        let check = parse_str::<Check>("check { a equal 10 }")
+            .unwrap()
+            .set_options(&config)
+            .set_case_name(&Ident::new("flaky", proc_macro2::Span::call_site()))
+            .to_token_stream()
+            .to_string();
+        assert!(check.contains("record_unstable_failure"));
+        assert!(check.contains("\"flaky\""));
+        assert!(check.contains("join (\"unstable.jsonl\")"));
+    }
+
+    #[test]
+    fn test_check_result_report() {
+        let config = parse_str::<Config>(
+            "cfg { comment = simple, unstable = true, result report = \"results.jsonl\" }",
+        )
+        .unwrap();
+        let check = parse_str::<Check>("check { a equal 10 }")
+            .unwrap()
+            .set_options(&config)
+            .set_case_name(&Ident::new("flaky", proc_macro2::Span::call_site()))
+            .to_token_stream()
+            .to_string();
+        assert!(check.contains("note_unstable_failure (__rusty_run"));
+        assert!(!check.contains("record_unstable_failure"));
+    }
+
+    #[test]
+    fn test_to_assertion() {
+        let conditions = parse_str::<Conditions>("a equal 10").unwrap();
+        let assertion = Check::new(conditions)
+            .to_assertion(Ident::new("debug_assert", proc_macro2::Span::call_site()))
+            .to_string();
+        let result = parse_str::<TS>("debug_assert!((a==10),\"a equal 10 where, a={:?}\" ,a )")
+            .unwrap()
+            .to_string();
+        assert_eq!(assertion, result);
+    }
+}
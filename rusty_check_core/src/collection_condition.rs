@@ -0,0 +1,270 @@
+use crate::helpers::{get_idents, Comment, ToComment};
+
+use super::{configure::CommentType, keywords as kw};
+use proc_macro2::TokenStream as TS;
+use quote::{quote, ToTokens};
+use std::fmt;
+use syn::parse::Parse;
+
+/// Represents a property that must hold for a whole collection.
+///
+/// Variants:
+/// - `Sorted`: Every element is ordered relative to the previous one, optionally
+///   comparing the result of a `key` function and optionally in `descending` order.
+/// - `UniqueElements`: No element is equal to an element before it.
+#[derive(Clone, Debug)]
+pub enum CollectionProperty {
+    Sorted {
+        key: Option<Box<syn::Expr>>,
+        descending: bool,
+    },
+    UniqueElements,
+}
+
+/// Represents a condition over a single slice-like collection.
+///
+/// A `CollectionCondition` consists of:
+/// - `collection`: The expression being checked, it has to support indexing with `[..]`.
+/// - `property`: The property checked, represented as a [`CollectionProperty`].
+#[derive(Clone, Debug)]
+pub struct CollectionCondition {
+    pub collection: syn::Expr,
+    pub property: CollectionProperty,
+}
+
+impl CollectionCondition {
+    /// Generates an expression evaluating to the index of the first element
+    /// breaking the property, or `None` when the property holds.
+    fn first_violation(&self) -> TS {
+        let collection = &self.collection;
+        match &self.property {
+            CollectionProperty::Sorted { key, descending } => {
+                let (previous, next) = match key {
+                    Some(key) => (
+                        quote! {(#key)(&__rusty_pair[0])},
+                        quote! {(#key)(&__rusty_pair[1])},
+                    ),
+                    None => (quote! {__rusty_pair[0]}, quote! {__rusty_pair[1]}),
+                };
+                let ordered = if *descending {
+                    quote! {#previous >= #next}
+                } else {
+                    quote! {#previous <= #next}
+                };
+                quote! {{
+                    let __rusty_items = &(#collection)[..];
+                    __rusty_items
+                        .windows(2)
+                        .position(|__rusty_pair| !(#ordered))
+                        .map(|__rusty_index| __rusty_index + 1)
+                }}
+            }
+            CollectionProperty::UniqueElements => quote! {{
+                let __rusty_items = &(#collection)[..];
+                __rusty_items
+                    .iter()
+                    .enumerate()
+                    .position(|(__rusty_index, __rusty_element)| {
+                        __rusty_items[..__rusty_index].contains(__rusty_element)
+                    })
+            }},
+        }
+    }
+}
+
+/// Checks if the input stream represents a `CollectionCondition`,
+/// an expression followed by `is` or `has unique`.
+pub fn is_collection_condition(input: &syn::parse::ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<syn::Expr>().is_ok()
+        && (fork.peek(kw::is) || (fork.peek(kw::has) && fork.peek2(kw::unique)))
+}
+
+impl Parse for CollectionProperty {
+    /// Parses a `CollectionProperty` from the input stream.
+    ///
+    /// Recognizes the following patterns:
+    /// - `is sorted [by <key>] [descending]` -> `CollectionProperty::Sorted`
+    /// - `has unique elements` -> `CollectionProperty::UniqueElements`
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::has) {
+            input.parse::<kw::has>()?;
+            input.parse::<kw::unique>()?;
+            input.parse::<kw::elements>()?;
+            return Ok(CollectionProperty::UniqueElements);
+        }
+        input.parse::<kw::is>()?;
+        input.parse::<kw::sorted>()?;
+        let key = if input.peek(kw::by) {
+            input.parse::<kw::by>()?;
+            Some(Box::new(input.parse::<syn::Expr>()?))
+        } else {
+            None
+        };
+        let descending = if input.peek(kw::descending) {
+            input.parse::<kw::descending>()?;
+            true
+        } else {
+            false
+        };
+        Ok(CollectionProperty::Sorted { key, descending })
+    }
+}
+
+impl Parse for CollectionCondition {
+    /// Parses a `CollectionCondition` from the input stream.
+    ///
+    /// A `CollectionCondition` consists of:
+    /// - A collection expression.
+    /// - A `CollectionProperty`.
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let collection = input.parse::<syn::Expr>()?;
+        let property = input.parse::<CollectionProperty>()?;
+        Ok(CollectionCondition {
+            collection,
+            property,
+        })
+    }
+}
+
+impl ToTokens for CollectionCondition {
+    /// Converts a `CollectionCondition` into its token representation.
+    ///
+    /// The generated expression is `true` when no element breaks the property.
+    fn to_tokens(&self, tokens: &mut TS) {
+        let first_violation = self.first_violation();
+        tokens.extend(quote! {
+            (#first_violation.is_none())
+        });
+    }
+}
+
+impl fmt::Display for CollectionProperty {
+    /// Formats a `CollectionProperty` as a human-readable string.
+    ///
+    /// - `CollectionProperty::Sorted` -> `"is sorted [by <key>] [descending]"`
+    /// - `CollectionProperty::UniqueElements` -> `"has unique elements"`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectionProperty::Sorted { key, descending } => {
+                write!(f, "is sorted")?;
+                if let Some(key) = key {
+                    write!(f, " by {}", key.to_token_stream())?;
+                }
+                if *descending {
+                    write!(f, " descending")?;
+                }
+                Ok(())
+            }
+            CollectionProperty::UniqueElements => write!(f, "has unique elements"),
+        }
+    }
+}
+
+impl fmt::Display for CollectionCondition {
+    /// Formats a `CollectionCondition` as a human-readable string.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.collection.to_token_stream(), self.property)
+    }
+}
+
+impl ToComment for CollectionCondition {
+    /// Converts a `CollectionCondition` into a `Comment` object.
+    ///
+    /// With `CommentType::ShowValues` the `Comment` also points at the first
+    /// element breaking the property.
+    fn to_comment(&self, comment_type: CommentType) -> Comment {
+        match comment_type {
+            CommentType::Simple => Comment {
+                string: self.to_string(),
+                values: vec![],
+                details: vec![],
+            },
+            CommentType::ShowValues => Comment {
+                string: self.to_string(),
+                values: get_idents(&self.collection.to_token_stream()),
+                details: vec![("first violating index".to_owned(), self.first_violation())],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_str;
+
+    #[test]
+    fn test_parse_sorted() {
+        let condition: CollectionCondition = parse_str("items is sorted").unwrap();
+        assert!(matches!(
+            condition.property,
+            CollectionProperty::Sorted {
+                key: None,
+                descending: false
+            }
+        ));
+        assert_eq!(condition.to_string(), "items is sorted");
+    }
+
+    #[test]
+    fn test_parse_sorted_by_key_descending() {
+        let condition: CollectionCondition =
+            parse_str("tasks is sorted by |t| t.id descending").unwrap();
+        match &condition.property {
+            CollectionProperty::Sorted { key, descending } => {
+                assert_eq!(key.to_token_stream().to_string(), str_to_tokens("|t| t.id"));
+                assert!(descending);
+            }
+            _ => panic!("Expected Sorted"),
+        }
+        assert_eq!(
+            condition.to_string(),
+            "tasks is sorted by | t | t . id descending"
+        );
+    }
+
+    #[test]
+    fn test_parse_unique_elements() {
+        let condition: CollectionCondition = parse_str("items has unique elements").unwrap();
+        assert!(matches!(
+            condition.property,
+            CollectionProperty::UniqueElements
+        ));
+        assert_eq!(condition.to_string(), "items has unique elements");
+    }
+
+    #[test]
+    fn test_to_tokens_sorted() {
+        let condition: CollectionCondition = parse_str("items is sorted").unwrap();
+        assert_eq!(
+            condition.to_token_stream().to_string(),
+            str_to_tokens(
+                "({
+                    let __rusty_items = &(items)[..];
+                    __rusty_items
+                        .windows(2)
+                        .position(|__rusty_pair| !(__rusty_pair[0] <= __rusty_pair[1]))
+                        .map(|__rusty_index| __rusty_index + 1)
+                }.is_none())"
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_comment_points_at_first_violation() {
+        let condition: CollectionCondition = parse_str("items has unique elements").unwrap();
+        let comment = condition.to_comment(CommentType::ShowValues);
+        assert_eq!(comment.values.len(), 1);
+        assert_eq!(comment.details.len(), 1);
+        assert_eq!(comment.details[0].0, "first violating index");
+
+        let comment = condition.to_comment(CommentType::Simple);
+        assert!(comment.values.is_empty());
+        assert!(comment.details.is_empty());
+    }
+
+    fn str_to_tokens(code: &str) -> String {
+        parse_str::<TS>(code).unwrap().to_string()
+    }
+}
@@ -15,6 +15,12 @@ pub struct Compute {
     rust_code: TS,
 }
 
+impl Compute {
+    pub fn rust_code(&self) -> &TS {
+        &self.rust_code
+    }
+}
+
 impl Parse for Compute {
     /// Parses a `Compute` block from the input stream.
     ///
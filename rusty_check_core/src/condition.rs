@@ -1,8 +1,13 @@
-use crate::dsl::proc_macros::helpers::{Comment, ToComment};
+use crate::helpers::{misspelled_keyword_error, unknown_keyword_error, Comment, ToComment};
 
-use super::{super::helpers::get_idents, configure::CommentType, keywords as kw};
+use super::{configure::CommentType, helpers::get_idents, keywords as kw};
 use proc_macro2::TokenStream as TS;
 use quote::{quote, ToTokens};
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt,
+    hash::{Hash, Hasher},
+};
 use syn::parse::Parse;
 
 /// Represents a logical or comparison operator in a condition.
@@ -11,6 +16,13 @@ use syn::parse::Parse;
 /// - `Equal`: Represents the `==` operator.
 /// - `EqualOr`: Represents `<=` or `>=` depending on the inner [`OtherSymbol`].
 /// - `Other`: Represents `<` or `>`.
+/// - `EqualElementwise`: Represents `==` between two slice-like collections.
+/// - `EqualIgnoringOrder`: Compares the elements of two collections as multisets.
+/// - `SameElementsAs`: Compares the elements of two collections as sets.
+/// - `StartsWith`: Represents a call to `starts_with` on the left-hand side.
+/// - `Matches`: Represents `matches!`, with the right-hand side used as a pattern.
+/// - `Satisfies`: Represents a call to `rusty_check_runtime::Matcher::matches`,
+///   with the right-hand side used as the matcher.
 /// - `Not`: Represents a negation of another [`Symbol`], such as `!=` or logical inversions.
 ///
 /// represents grammar from this diagram:
@@ -20,9 +32,45 @@ pub enum Symbol {
     Equal,
     EqualOr(OtherSymbol),
     Other(OtherSymbol),
+    EqualElementwise,
+    EqualIgnoringOrder,
+    SameElementsAs,
+    StartsWith,
+    Matches,
+    Satisfies,
     Not(Box<Symbol>),
 }
 
+impl Symbol {
+    /// Checks if the symbol compares its operands element by element.
+    pub fn is_elementwise(&self) -> bool {
+        match self {
+            Symbol::EqualElementwise => true,
+            Symbol::Not(symbol) => symbol.is_elementwise(),
+            _ => false,
+        }
+    }
+    /// Checks if the symbol compares the elements of its operands regardless of their order.
+    pub fn is_unordered(&self) -> bool {
+        match self {
+            Symbol::EqualIgnoringOrder | Symbol::SameElementsAs => true,
+            Symbol::Not(symbol) => symbol.is_unordered(),
+            _ => false,
+        }
+    }
+    /// Removes leading negations, returning whether the symbol was negated
+    /// an odd number of times together with the negated symbol.
+    pub fn strip_negations(&self) -> (bool, &Symbol) {
+        match self {
+            Symbol::Not(symbol) => {
+                let (negated, symbol) = symbol.strip_negations();
+                (!negated, symbol)
+            }
+            _ => (false, self),
+        }
+    }
+}
+
 /// Represents a non-equality comparison operator.
 ///
 /// Variants:
@@ -39,13 +87,16 @@ pub enum OtherSymbol {
 ///
 /// A `Condition` consists of:
 /// - `left`: The left-hand side expression.
-/// - `symbol`: The operator, represented as a [`Symbol`].
+/// - `symbol`: The operator, represented as a `Symbol`.
 /// - `right`: The right-hand side expression.
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Condition {
+    /// The left-hand side expression.
     pub left: syn::Expr,
+    /// The operator.
     pub symbol: Symbol,
+    /// The right-hand side expression.
     pub right: syn::Expr,
 }
 
@@ -74,24 +125,75 @@ impl Parse for Symbol {
     /// Recognizes the following patterns:
     /// - `equal or less than` -> `Symbol::EqualOr(OtherSymbol::Less)`
     /// - `equal or greater than` -> `Symbol::EqualOr(OtherSymbol::Greater)`
+    /// - `equal elementwise` -> `Symbol::EqualElementwise`
+    /// - `equal ignoring order` -> `Symbol::EqualIgnoringOrder`
+    /// - `same elements as` -> `Symbol::SameElementsAs`
+    /// - `starts with` -> `Symbol::StartsWith`
+    /// - `matches` -> `Symbol::Matches`
+    /// - `satisfies` -> `Symbol::Satisfies`
     /// - `equal` -> `Symbol::Equal`
     /// - `not <symbol>` -> `Symbol::Not(Box<Symbol>)`
     /// - `<` or `>` -> `Symbol::Other(OtherSymbol)`
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` spanning the unknown symbol and suggesting the closest known one.
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if input.peek(kw::equal) && input.peek2(kw::or) {
-            input.parse::<kw::equal>()?;
-            input.parse::<kw::or>()?;
-            Ok(Symbol::EqualOr(input.parse::<OtherSymbol>()?))
-        } else if input.peek(kw::equal) {
-            input.parse::<kw::equal>()?;
-            Ok(Symbol::Equal)
-        } else if input.peek(kw::not) {
-            input.parse::<kw::not>()?;
-            Ok(Symbol::Not(input.parse()?))
-        } else {
-            let other = input.parse::<OtherSymbol>()?;
-            Ok(Symbol::Other(other))
-        }
+        let start = input.fork();
+        parse_symbol(input).map_err(|_| {
+            let negated: Vec<String> = kw::SYMBOLS
+                .iter()
+                .map(|symbol| format!("not {}", symbol))
+                .collect();
+            let candidates: Vec<&str> = kw::SYMBOLS
+                .iter()
+                .copied()
+                .chain(negated.iter().map(String::as_str))
+                .collect();
+            misspelled_keyword_error(&start, "symbol", &candidates)
+                .unwrap_or_else(|| unknown_keyword_error(&start, "symbol", kw::SYMBOLS))
+        })
+    }
+}
+
+/// Parses a `Symbol` from the input stream, see [`Symbol::parse`].
+fn parse_symbol(input: syn::parse::ParseStream) -> syn::Result<Symbol> {
+    if input.peek(kw::equal) && input.peek2(kw::or) {
+        input.parse::<kw::equal>()?;
+        input.parse::<kw::or>()?;
+        Ok(Symbol::EqualOr(input.parse::<OtherSymbol>()?))
+    } else if input.peek(kw::equal) && input.peek2(kw::elementwise) {
+        input.parse::<kw::equal>()?;
+        input.parse::<kw::elementwise>()?;
+        Ok(Symbol::EqualElementwise)
+    } else if input.peek(kw::equal) && input.peek2(kw::ignoring) {
+        input.parse::<kw::equal>()?;
+        input.parse::<kw::ignoring>()?;
+        input.parse::<kw::order>()?;
+        Ok(Symbol::EqualIgnoringOrder)
+    } else if input.peek(kw::same) {
+        input.parse::<kw::same>()?;
+        input.parse::<kw::elements>()?;
+        input.parse::<syn::Token![as]>()?;
+        Ok(Symbol::SameElementsAs)
+    } else if input.peek(kw::starts) {
+        input.parse::<kw::starts>()?;
+        input.parse::<kw::with>()?;
+        Ok(Symbol::StartsWith)
+    } else if input.peek(kw::matches) {
+        input.parse::<kw::matches>()?;
+        Ok(Symbol::Matches)
+    } else if input.peek(kw::satisfies) {
+        input.parse::<kw::satisfies>()?;
+        Ok(Symbol::Satisfies)
+    } else if input.peek(kw::equal) {
+        input.parse::<kw::equal>()?;
+        Ok(Symbol::Equal)
+    } else if input.peek(kw::not) {
+        input.parse::<kw::not>()?;
+        Ok(Symbol::Not(Box::new(parse_symbol(input)?)))
+    } else {
+        let other = input.parse::<OtherSymbol>()?;
+        Ok(Symbol::Other(other))
     }
 }
 
@@ -113,14 +215,28 @@ impl ToTokens for Symbol {
     /// Converts a `Symbol` into its token representation.
     ///
     /// Handles all variants of `Symbol`, including nested `Not` symbols.
+    /// Method-like symbols are converted into the name of the method or macro implementing them,
+    /// their negation is applied by [`Condition`].
     fn to_tokens(&self, tokens: &mut TS) {
         let symbol = match self {
             Symbol::Equal => quote! {==},
             Symbol::EqualOr(OtherSymbol::Less) => quote! {<=},
             Symbol::EqualOr(OtherSymbol::Greater) => quote! {>=},
             Symbol::Other(other_symbol) => other_symbol.to_token_stream(),
+            Symbol::EqualElementwise | Symbol::EqualIgnoringOrder | Symbol::SameElementsAs => {
+                quote! {==}
+            }
+            Symbol::StartsWith => quote! {starts_with},
+            Symbol::Matches => quote! {matches},
+            Symbol::Satisfies => quote! {::rusty_check_runtime::Matcher::matches},
             Symbol::Not(symbol) => match symbol.as_ref() {
-                Symbol::Equal => quote! {!=},
+                Symbol::StartsWith | Symbol::Matches | Symbol::Satisfies => {
+                    symbol.to_token_stream()
+                }
+                Symbol::Equal
+                | Symbol::EqualElementwise
+                | Symbol::EqualIgnoringOrder
+                | Symbol::SameElementsAs => quote! {!=},
                 Symbol::EqualOr(OtherSymbol::Less) => quote! {>},
                 Symbol::EqualOr(OtherSymbol::Greater) => quote! {<},
                 Symbol::Other(other_symbol) => match other_symbol {
@@ -157,54 +273,188 @@ impl ToTokens for Condition {
     /// Converts a `Condition` into its token representation.
     ///
     /// Combines the left-hand side, operator, and right-hand side into a single token stream.
+    /// Elementwise symbols compare both sides as slices, unordered symbols
+    /// compare the elements missing from each side and method-like symbols
+    /// call the method or macro implementing them.
     fn to_tokens(&self, tokens: &mut TS) {
         let left = &self.left;
         let symbol = &self.symbol;
         let right = &self.right;
-        tokens.extend(quote! {
-            ( #left #symbol #right )
-        });
+        let (negated, base_symbol) = self.symbol.strip_negations();
+        let negation = if negated {
+            quote! {!}
+        } else {
+            TS::new()
+        };
+        let condition = match base_symbol {
+            Symbol::EqualIgnoringOrder | Symbol::SameElementsAs => {
+                let condition = self.unordered_condition(negation);
+                quote! { ( #condition ) }
+            }
+            Symbol::EqualElementwise => quote! { ( (#left)[..] #symbol (#right)[..] ) },
+            Symbol::StartsWith => quote! { ( #negation (#left).#symbol(#right) ) },
+            Symbol::Matches => quote! { ( #negation #symbol!(#left, #right) ) },
+            Symbol::Satisfies => quote! { ( #negation #symbol(&(#right), &(#left)) ) },
+            _ => quote! { ( #left #symbol #right ) },
+        };
+        tokens.extend(condition);
     }
 }
 
-impl ToString for OtherSymbol {
-    /// Converts an `OtherSymbol` into a human-readable string.
+impl fmt::Display for OtherSymbol {
+    /// Formats an `OtherSymbol` as a human-readable string.
     ///
     /// - `OtherSymbol::Less` -> `"less than"`
     /// - `OtherSymbol::Greater` -> `"greater than"`
-    fn to_string(&self) -> String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            OtherSymbol::Less => "less than".to_owned(),
-            OtherSymbol::Greater => "greater than".to_owned(),
+            OtherSymbol::Less => write!(f, "less than"),
+            OtherSymbol::Greater => write!(f, "greater than"),
         }
     }
 }
 
-impl ToString for Symbol {
-    /// Converts a `Symbol` into a human-readable string.
+impl fmt::Display for Symbol {
+    /// Formats a `Symbol` as a human-readable string.
     ///
     /// Handles all variants of `Symbol`, including nested `Not` symbols.
-    fn to_string(&self) -> String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Symbol::Equal => "equal".to_string(),
-            Symbol::EqualOr(OtherSymbol::Less) => "equal or less than".to_owned(),
-            Symbol::EqualOr(OtherSymbol::Greater) => "equal or greater than".to_owned(),
-            Symbol::Other(other_symbol) => other_symbol.to_string(),
-            Symbol::Not(symbol) => "not ".to_owned() + &symbol.to_string(),
+            Symbol::Equal => write!(f, "equal"),
+            Symbol::EqualOr(OtherSymbol::Less) => write!(f, "equal or less than"),
+            Symbol::EqualOr(OtherSymbol::Greater) => write!(f, "equal or greater than"),
+            Symbol::Other(other_symbol) => write!(f, "{}", other_symbol),
+            Symbol::EqualElementwise => write!(f, "equal elementwise"),
+            Symbol::EqualIgnoringOrder => write!(f, "equal ignoring order"),
+            Symbol::SameElementsAs => write!(f, "same elements as"),
+            Symbol::StartsWith => write!(f, "starts with"),
+            Symbol::Matches => write!(f, "matches"),
+            Symbol::Satisfies => write!(f, "satisfies"),
+            Symbol::Not(symbol) => write!(f, "not {}", symbol),
         }
     }
 }
 
-impl ToString for Condition {
-    /// Converts a `Condition` into a human-readable string.
+impl fmt::Display for Condition {
+    /// Formats a `Condition` as a human-readable string.
     ///
     /// Combines the string representations of the left-hand side, operator, and right-hand side.
-    fn to_string(&self) -> String {
-        self.left.to_token_stream().to_string()
-            + " "
-            + &self.symbol.to_string()
-            + " "
-            + &self.right.to_token_stream().to_string()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.left.to_token_stream(),
+            self.symbol,
+            self.right.to_token_stream()
+        )
+    }
+}
+
+impl Condition {
+    /// Generates an expression evaluating to the first index at which the two
+    /// collections differ, or `None` if they are equal elementwise.
+    fn first_mismatch(&self) -> TS {
+        let left = &self.left;
+        let right = &self.right;
+        quote! {{
+            let (__rusty_left, __rusty_right) = (&(#left)[..], &(#right)[..]);
+            __rusty_left
+                .iter()
+                .zip(__rusty_right.iter())
+                .position(|(__rusty_l, __rusty_r)| __rusty_l != __rusty_r)
+                .or((__rusty_left.len() != __rusty_right.len())
+                    .then(|| __rusty_left.len().min(__rusty_right.len())))
+        }}
+    }
+
+    /// Returns the identifier of the cell an unordered condition stores its collected
+    /// operands in when it does not hold, so the failure message can show their
+    /// difference without evaluating them again.
+    ///
+    /// The cell is declared by the check around the condition, see
+    /// [`Conditions::unordered_slots`](super::conditions::Conditions::unordered_slots).
+    pub(crate) fn unordered_slot(&self) -> syn::Ident {
+        let mut hasher = DefaultHasher::new();
+        self.to_string().hash(&mut hasher);
+        quote::format_ident!("__rusty_unordered_{:x}", hasher.finish())
+    }
+
+    /// Generates statements binding the elements of `__rusty_right` missing from
+    /// `__rusty_left` to `__rusty_missing` and the elements of `__rusty_left` missing
+    /// from `__rusty_right` to `__rusty_extra`.
+    ///
+    /// `SameElementsAs` ignores how many times an element occurs,
+    /// `EqualIgnoringOrder` matches every element at most once.
+    fn unordered_difference(&self) -> TS {
+        if let (_, Symbol::SameElementsAs) = self.symbol.strip_negations() {
+            quote! {
+                let __rusty_missing: Vec<_> = __rusty_right
+                    .iter()
+                    .filter(|__rusty_r| !__rusty_left.iter().any(|__rusty_l| *__rusty_l == **__rusty_r))
+                    .collect();
+                let __rusty_extra: Vec<_> = __rusty_left
+                    .iter()
+                    .filter(|__rusty_l| !__rusty_right.iter().any(|__rusty_r| **__rusty_l == *__rusty_r))
+                    .collect();
+            }
+        } else {
+            quote! {
+                let mut __rusty_missing: Vec<_> = __rusty_right.iter().collect();
+                let mut __rusty_extra = Vec::new();
+                for __rusty_l in __rusty_left.iter() {
+                    match __rusty_missing
+                        .iter()
+                        .position(|__rusty_r| *__rusty_l == **__rusty_r)
+                    {
+                        Some(__rusty_index) => {
+                            __rusty_missing.remove(__rusty_index);
+                        }
+                        None => __rusty_extra.push(__rusty_l),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates the expression of an unordered condition.
+    ///
+    /// Each operand is collected once into an owned `Vec`, so both sides yield their
+    /// elements by value, and the `Vec`s are moved into the cell of the condition
+    /// if it does not hold.
+    fn unordered_condition(&self, negation: TS) -> TS {
+        let left = &self.left;
+        let right = &self.right;
+        let slot = self.unordered_slot();
+        let difference = self.unordered_difference();
+        quote! {{
+            let __rusty_left = (#left).into_iter().collect::<Vec<_>>();
+            let __rusty_right = (#right).into_iter().collect::<Vec<_>>();
+            let __rusty_holds = #negation {
+                #difference
+                __rusty_missing.is_empty() && __rusty_extra.is_empty()
+            };
+            if !__rusty_holds {
+                let _ = #slot.set((__rusty_left, __rusty_right));
+            }
+            __rusty_holds
+        }}
+    }
+
+    /// Generates an expression evaluating to `binding`, `__rusty_missing` or
+    /// `__rusty_extra`, computed from the operands stored in the cell of the condition,
+    /// or to an empty `Vec` if the condition was not evaluated or held.
+    fn stored_difference(&self, binding: TS) -> TS {
+        let slot = self.unordered_slot();
+        let compute = self.unordered_difference();
+        quote! {
+            match #slot.get() {
+                Some((__rusty_left, __rusty_right)) => {
+                    #compute
+                    #binding
+                }
+                None => Vec::new(),
+            }
+        }
     }
 }
 
@@ -213,7 +463,13 @@ impl ToComment for Condition {
     ///
     /// The `Comment` includes:
     /// - A string representation of the condition.
-    /// - A list of identifiers used in the left-hand and right-hand expressions.
+    /// - A list of identifiers used in the left-hand and right-hand expressions,
+    ///   the right-hand sides of `matches` and `satisfies` are skipped,
+    ///   unordered symbols show no whole collections.
+    /// - For elementwise symbols, the first index at which the collections differ.
+    /// - For unordered symbols, the elements missing from the left collection
+    ///   and the extra elements it has.
+    /// - For `satisfies`, the mismatch description of the matcher.
     fn to_comment(&self, comment_type: CommentType) -> Comment {
         let left = &self.left;
         let right = &self.right;
@@ -222,18 +478,54 @@ impl ToComment for Condition {
             CommentType::Simple => Comment {
                 string: condition_string,
                 values: vec![],
+                details: vec![],
             },
             CommentType::ShowValues => {
-                let left_idents = get_idents(&left.to_token_stream());
-                let right_idents = get_idents(&right.to_token_stream());
-                let values = vec![left_idents, right_idents]
+                let (left_idents, right_idents) = match self.symbol.strip_negations() {
+                    (_, Symbol::EqualIgnoringOrder | Symbol::SameElementsAs) => (vec![], vec![]),
+                    (_, Symbol::Matches | Symbol::Satisfies) => {
+                        (get_idents(&left.to_token_stream()), vec![])
+                    }
+                    _ => (
+                        get_idents(&left.to_token_stream()),
+                        get_idents(&right.to_token_stream()),
+                    ),
+                };
+                let values = [left_idents, right_idents]
                     .concat()
                     .iter()
                     .map(|i| i.to_token_stream())
                     .collect();
+                let details = if self.symbol.is_elementwise() {
+                    vec![("first mismatch index".to_owned(), self.first_mismatch())]
+                } else if self.symbol.is_unordered() {
+                    vec![
+                        (
+                            "missing".to_owned(),
+                            self.stored_difference(quote! {__rusty_missing}),
+                        ),
+                        (
+                            "extra".to_owned(),
+                            self.stored_difference(quote! {__rusty_extra}),
+                        ),
+                    ]
+                } else if let (false, Symbol::Satisfies) = self.symbol.strip_negations() {
+                    vec![(
+                        "mismatch".to_owned(),
+                        quote! {
+                            format_args!(
+                                "{}",
+                                ::rusty_check_runtime::Matcher::describe_mismatch(&(#right), &(#left))
+                            )
+                        },
+                    )]
+                } else {
+                    vec![]
+                };
                 Comment {
                     string: condition_string,
-                    values: values,
+                    values,
+                    details,
                 }
             }
         }
@@ -270,6 +562,39 @@ mod tests {
 
         let symbol: Symbol = parse_str("not equal").unwrap();
         assert_eq!(symbol, Symbol::Not(Box::new(Symbol::Equal)));
+
+        let symbol: Symbol = parse_str("equal elementwise").unwrap();
+        assert_eq!(symbol, Symbol::EqualElementwise);
+
+        let symbol: Symbol = parse_str("equal ignoring order").unwrap();
+        assert_eq!(symbol, Symbol::EqualIgnoringOrder);
+
+        let symbol: Symbol = parse_str("not same elements as").unwrap();
+        assert_eq!(symbol, Symbol::Not(Box::new(Symbol::SameElementsAs)));
+
+        let symbol: Symbol = parse_str("starts with").unwrap();
+        assert_eq!(symbol, Symbol::StartsWith);
+
+        let symbol: Symbol = parse_str("not matches").unwrap();
+        assert_eq!(symbol, Symbol::Not(Box::new(Symbol::Matches)));
+
+        let symbol: Symbol = parse_str("satisfies").unwrap();
+        assert_eq!(symbol, Symbol::Satisfies);
+    }
+
+    #[test]
+    fn test_parse_symbol_suggestion() {
+        let error = parse_str::<Symbol>("not equl").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown symbol `not equl`, did you mean `not equal`?"
+        );
+
+        let error = parse_str::<Symbol>("equal or grater than").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown symbol `equal or grater than`, did you mean `equal or greater than`?"
+        );
     }
 
     #[test]
@@ -586,6 +911,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_tokens_condition_elementwise() {
+        let condition: Condition = parse_str("a equal elementwise b").unwrap();
+        let token_stream: TS = quote! {#condition};
+        assert_eq!(
+            token_stream.to_string(),
+            str_to_tokens("( (a)[..] == (b)[..] )")
+        );
+
+        let condition: Condition = parse_str("a not equal elementwise b").unwrap();
+        let token_stream: TS = quote! {#condition};
+        assert_eq!(
+            token_stream.to_string(),
+            str_to_tokens("( (a)[..] != (b)[..] )")
+        );
+        assert_eq!(condition.to_string(), "a not equal elementwise b");
+    }
+
+    #[test]
+    fn test_to_tokens_condition_method_like() {
+        let condition: Condition = parse_str("name starts with \"a\"").unwrap();
+        let token_stream: TS = quote! {#condition};
+        assert_eq!(
+            token_stream.to_string(),
+            str_to_tokens("( (name).starts_with(\"a\") )")
+        );
+
+        let condition: Condition = parse_str("state not matches State::Done").unwrap();
+        let token_stream: TS = quote! {#condition};
+        assert_eq!(
+            token_stream.to_string(),
+            str_to_tokens("( ! matches!(state, State::Done) )")
+        );
+
+        let condition: Condition = parse_str("email satisfies is_valid_email()").unwrap();
+        let token_stream: TS = quote! {#condition};
+        assert_eq!(
+            token_stream.to_string(),
+            str_to_tokens(
+                "( ::rusty_check_runtime::Matcher::matches(&(is_valid_email()), &(email)) )"
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_comment_condition_unordered() {
+        let condition: Condition = parse_str("a equal ignoring order b").unwrap();
+        assert_eq!(condition.to_string(), "a equal ignoring order b");
+        let comment = condition.to_comment(CommentType::ShowValues);
+        let labels: Vec<&str> = comment
+            .details
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["missing", "extra"]);
+        assert!(comment.values.is_empty());
+    }
+
+    #[test]
+    fn test_to_tokens_condition_unordered_collects_operands_once() {
+        let condition: Condition = parse_str("v same elements as w.iter().copied()").unwrap();
+        let slot = condition.unordered_slot();
+        let tokens = condition.to_token_stream().to_string();
+        assert!(tokens.contains(&str_to_tokens("(v).into_iter().collect")));
+        assert!(tokens.contains(&str_to_tokens("(w.iter().copied()).into_iter().collect")));
+        assert!(tokens.contains(&format!("{} . set ((__rusty_left , __rusty_right))", slot)));
+
+        let comment = condition.to_comment(CommentType::ShowValues);
+        for (_, detail) in comment.details {
+            let detail = detail.to_string();
+            assert!(detail.contains(&format!("{} . get ()", slot)));
+            assert!(!detail.contains("copied"));
+        }
+    }
+
     fn str_to_tokens(code: &str) -> String {
         parse_str::<TS>(code).unwrap().to_string()
     }
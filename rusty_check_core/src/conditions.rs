@@ -0,0 +1,645 @@
+use super::collection_condition::{is_collection_condition, CollectionCondition};
+use super::configure::CommentType;
+use super::fields_condition::{is_fields_condition, FieldsCondition};
+use super::helpers::{
+    filter_out_details_with_idents, filter_out_streams_with_idents, get_pat_idents,
+    unknown_keyword_error, Comment, ToComment,
+};
+use super::{condition::Condition, keywords as kw};
+use proc_macro2::TokenStream as TS;
+use quote::{quote, ToTokens};
+use std::fmt;
+use syn::{parse::Parse, Token};
+
+/// Represents different types of conditions in the RustyCheck DSL.
+///
+/// Variants:
+/// - `LoopCondition`: A condition that involves iterating over a collection.
+/// - `CompoundCondition`: A condition composed of two sub-conditions joined by a logical operator.
+/// - `CollectionCondition`: A single condition over a whole collection.
+/// - `FieldsCondition`: A single condition over several fields of a value.
+/// - `Condition`: A single condition.
+///
+/// represents grammar from this diagram:
+///
+#[derive(Clone, Debug)]
+pub enum Conditions {
+    /// A condition that involves iterating over a collection.
+    LoopCondition {
+        /// The type of loop (e.g., `ForAny` or `ForEach`).
+        loop_type: LoopType,
+        /// The collection being iterated over.
+        collection: syn::Expr,
+        /// The pattern bound to each element being iterated.
+        element: syn::Pat,
+        /// The optional identifier bound to the position of each element.
+        index: Option<syn::Ident>,
+        /// The condition applied to each element.
+        condition: Box<Conditions>,
+    },
+    /// A condition composed of two sub-conditions joined by a logical operator.
+    CompoundCondition {
+        /// The left-hand side condition, a `Condition`, `CollectionCondition` or `FieldsCondition`.
+        left_condition: Box<Conditions>,
+        /// The logical operator joining the conditions (`And` or `Or`).
+        join: JoinType,
+        /// The right-hand side condition.
+        right_condition: Box<Conditions>,
+    },
+    /// A single condition over a whole collection.
+    CollectionCondition(CollectionCondition),
+    /// A single condition over several fields of a value.
+    FieldsCondition(FieldsCondition),
+    /// A single condition.
+    Condition(Condition),
+}
+
+/// Represents the type of loop used in a `LoopCondition`.
+///
+/// Variants:
+/// - `ForAny`: A loop that checks if any element satisfies the condition.
+/// - `ForEach`: A loop that checks if all elements satisfy the condition.
+/// - `ForEachConsecutive`: A loop that checks if all pairs of neighbouring elements satisfy the condition.
+#[derive(Clone, Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum LoopType {
+    ForAny,
+    ForEach,
+    ForEachConsecutive,
+}
+
+/// Represents the logical operator used in a `CompoundCondition`.
+///
+/// Variants:
+/// - `Or`: Logical OR (`||`).
+/// - `And`: Logical AND (`&&`).
+#[derive(Clone, Debug)]
+pub enum JoinType {
+    Or,
+    And,
+}
+
+impl Parse for JoinType {
+    /// Parses a `JoinType` from the input stream.
+    ///
+    /// Recognizes the keywords `and` and `or`.
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.parse::<kw::and>().is_ok() {
+            Ok(JoinType::And)
+        } else {
+            input.parse::<kw::or>()?;
+            Ok(JoinType::Or)
+        }
+    }
+}
+
+impl ToTokens for JoinType {
+    /// Converts a `JoinType` into its token representation.
+    ///
+    /// - `Or` -> `||`
+    /// - `And` -> `&&`
+    fn to_tokens(&self, tokens: &mut TS) {
+        let join_type = match self {
+            JoinType::Or => quote! {||},
+            JoinType::And => quote! {&&},
+        };
+        tokens.extend(join_type);
+    }
+}
+
+impl fmt::Display for JoinType {
+    /// Formats a `JoinType` as a human-readable string.
+    ///
+    /// - `Or` -> `"or"`
+    /// - `And` -> `"and"`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinType::Or => write!(f, "or"),
+            JoinType::And => write!(f, "and"),
+        }
+    }
+}
+
+impl fmt::Display for LoopType {
+    /// Formats a `LoopType` as a human-readable string.
+    ///
+    /// - `ForAny` -> `"for any"`
+    /// - `ForEach` -> `"for each"`
+    /// - `ForEachConsecutive` -> `"for each consecutive"`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopType::ForAny => write!(f, "for any"),
+            LoopType::ForEach => write!(f, "for each"),
+            LoopType::ForEachConsecutive => write!(f, "for each consecutive"),
+        }
+    }
+}
+
+impl Conditions {
+    /// Collects every `Condition`, including those nested in loops and compound conditions.
+    ///
+    /// Conditions inside a `FieldsCondition` are skipped, their left-hand sides
+    /// are relative to the checked value.
+    pub fn conditions(&self) -> Vec<&Condition> {
+        match self {
+            Conditions::LoopCondition { condition, .. } => condition.conditions(),
+            Conditions::CompoundCondition {
+                left_condition,
+                right_condition,
+                ..
+            } => {
+                let mut conditions = left_condition.conditions();
+                conditions.extend(right_condition.conditions());
+                conditions
+            }
+            Conditions::CollectionCondition(_) | Conditions::FieldsCondition(_) => vec![],
+            Conditions::Condition(condition) => vec![condition],
+        }
+    }
+
+    /// Generates the declarations of the cells the unordered conditions store their
+    /// operands in, to be placed before the conditions and their failure message.
+    pub(crate) fn unordered_slots(&self) -> TS {
+        let mut slots: Vec<syn::Ident> = self
+            .conditions()
+            .into_iter()
+            .cloned()
+            .chain(self.fields_conditions())
+            .filter(|condition| condition.symbol.is_unordered())
+            .map(|condition| condition.unordered_slot())
+            .collect();
+        slots.sort();
+        slots.dedup();
+        quote! {
+            #(let #slots = ::std::cell::OnceCell::new();)*
+        }
+    }
+
+    /// Collects the conditions of every `FieldsCondition`, resolved against the checked value.
+    fn fields_conditions(&self) -> Vec<Condition> {
+        match self {
+            Conditions::LoopCondition { condition, .. } => condition.fields_conditions(),
+            Conditions::CompoundCondition {
+                left_condition,
+                right_condition,
+                ..
+            } => [
+                left_condition.fields_conditions(),
+                right_condition.fields_conditions(),
+            ]
+            .concat(),
+            Conditions::FieldsCondition(condition) => condition.resolved_fields(),
+            Conditions::CollectionCondition(_) | Conditions::Condition(_) => vec![],
+        }
+    }
+}
+
+/// Checks if the input stream represents a `for each` loop.
+fn is_for_each(input: &syn::parse::ParseStream) -> bool {
+    input.peek(Token![for]) && input.peek2(kw::each)
+}
+
+/// Checks if the input stream represents a `for any` loop.
+fn is_in_any(input: &syn::parse::ParseStream) -> bool {
+    input.peek(Token![for]) && input.peek2(kw::any)
+}
+
+/// Parses a `for` loop condition from the input stream.
+///
+/// # Parameters
+/// - `input`: The parse stream to read from.
+/// - `loop_type`: The type of loop (`ForAny`, `ForEach` or `ForEachConsecutive`).
+///
+/// # Returns
+/// A `Conditions::LoopCondition` representing the parsed loop.
+fn parse_for_loop(input: syn::parse::ParseStream, loop_type: LoopType) -> syn::Result<Conditions> {
+    input.parse::<Token![for]>()?;
+    match loop_type {
+        LoopType::ForAny => {
+            input.parse::<kw::any>()?;
+        }
+        LoopType::ForEach => {
+            input.parse::<kw::each>()?;
+        }
+        LoopType::ForEachConsecutive => {
+            input.parse::<kw::each>()?;
+            input.parse::<kw::consecutive>()?;
+        }
+    }
+    let element = syn::Pat::parse_single(input)?;
+    let index = if input.peek(kw::at) {
+        input.parse::<kw::at>()?;
+        input.parse::<kw::index>()?;
+        Some(input.parse::<syn::Ident>()?)
+    } else {
+        None
+    };
+    input.parse::<Token![in]>()?;
+    let collection = input.parse::<syn::Expr>()?;
+    input.parse::<Token![,]>()?;
+    let conditions = input.parse::<Conditions>()?;
+    Ok(Conditions::LoopCondition {
+        collection,
+        element,
+        index,
+        loop_type,
+        condition: Box::new(conditions),
+    })
+}
+
+/// Parses a loop condition from the input stream.
+///
+/// Determines whether the loop is a `for each`, `for each consecutive` or `for any` loop
+/// and parses accordingly.
+///
+/// # Errors
+/// Returns a `syn::Error` suggesting the closest known loop if the loop is not recognized.
+fn parse_loop_condition(input: syn::parse::ParseStream) -> syn::Result<Conditions> {
+    if is_for_each(&input) && input.peek3(kw::consecutive) {
+        parse_for_loop(input, LoopType::ForEachConsecutive)
+    } else if is_for_each(&input) {
+        parse_for_loop(input, LoopType::ForEach)
+    } else if is_in_any(&input) {
+        parse_for_loop(input, LoopType::ForAny)
+    } else {
+        Err(unknown_keyword_error(input, "loop condition", kw::LOOPS))
+    }
+}
+
+impl Parse for Conditions {
+    /// Parses a `Conditions` instance from the input stream.
+    ///
+    /// Handles `LoopCondition`, `CompoundCondition`, `CollectionCondition`, `FieldsCondition`
+    /// and `Condition` variants.
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![for]) {
+            return parse_loop_condition(input);
+        }
+        let condition = if is_fields_condition(&input) {
+            Conditions::FieldsCondition(input.parse()?)
+        } else if is_collection_condition(&input) {
+            Conditions::CollectionCondition(input.parse()?)
+        } else {
+            Conditions::Condition(input.parse()?)
+        };
+        if input.peek(kw::or) || input.peek(kw::and) {
+            let join_type = input.parse::<JoinType>()?;
+            return Ok(Conditions::CompoundCondition {
+                left_condition: Box::new(condition),
+                join: join_type,
+                right_condition: Box::new(input.parse()?),
+            });
+        }
+        Ok(condition)
+    }
+}
+
+/// Generates a call of `method` on the neighbouring pairs of `collection`.
+///
+/// Inside `body` each pair is bound to `element` and, if present, the position
+/// of its first item to `index`.
+fn consecutive_pairs_call(
+    collection: &syn::Expr,
+    element: &syn::Pat,
+    index: &Option<syn::Ident>,
+    method: TS,
+    body: TS,
+) -> TS {
+    let (windows, args) = match index {
+        Some(index) => (
+            quote! {windows(2).enumerate()},
+            quote! {(#index, __rusty_pair)},
+        ),
+        None => (quote! {windows(2)}, quote! {__rusty_pair}),
+    };
+    quote! {
+        match (#collection).into_iter().collect::<Vec<_>>() {
+            __rusty_items => __rusty_items.#windows.#method(|#args| {
+                let #element = (&__rusty_pair[0], &__rusty_pair[1]);
+                #body
+            }),
+        }
+    }
+}
+
+impl ToTokens for Conditions {
+    /// Converts a `Conditions` instance into its token representation.
+    ///
+    /// Generates Rust code for the condition, including loops and compound conditions.
+    fn to_tokens(&self, tokens: &mut TS) {
+        let conditions = match self {
+            Conditions::LoopCondition {
+                loop_type,
+                collection,
+                element,
+                index,
+                condition,
+            } => {
+                let method = match loop_type {
+                    LoopType::ForEach | LoopType::ForEachConsecutive => quote! {all},
+                    LoopType::ForAny => quote! {any},
+                };
+                match (loop_type, index) {
+                    (LoopType::ForEachConsecutive, _) => consecutive_pairs_call(
+                        collection,
+                        element,
+                        index,
+                        method,
+                        condition.to_token_stream(),
+                    ),
+                    (_, Some(index)) => quote! {
+                        (#collection).into_iter().enumerate().#method(|(#index, #element)|#condition)
+                    },
+                    (_, None) => quote! { (#collection).into_iter().#method(|#element|#condition) },
+                }
+            }
+            Conditions::CompoundCondition {
+                left_condition,
+                join,
+                right_condition,
+            } => {
+                quote! { (#left_condition #join #right_condition) }
+            }
+            Conditions::CollectionCondition(condition) => condition.to_token_stream(),
+            Conditions::FieldsCondition(condition) => condition.to_token_stream(),
+            Conditions::Condition(condition) => condition.to_token_stream(),
+        };
+        tokens.extend(conditions);
+    }
+}
+
+impl fmt::Display for Conditions {
+    /// Formats a `Conditions` instance as a human-readable string.
+    ///
+    /// Handles all variants, including loops and compound conditions.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Conditions::LoopCondition {
+                loop_type,
+                collection,
+                element,
+                index,
+                condition,
+            } => {
+                write!(f, "{} {}", loop_type, element.to_token_stream())?;
+                if let Some(index) = index {
+                    write!(f, " at index {}", index)?;
+                }
+                write!(f, " in {}, {}", collection.to_token_stream(), condition)
+            }
+            Conditions::CompoundCondition {
+                left_condition,
+                join,
+                right_condition,
+            } => write!(f, "{} {} {}", left_condition, join, right_condition),
+            Conditions::CollectionCondition(condition) => write!(f, "{}", condition),
+            Conditions::FieldsCondition(condition) => write!(f, "{}", condition),
+            Conditions::Condition(condition) => write!(f, "{}", condition),
+        }
+    }
+}
+
+impl ToComment for Conditions {
+    /// Converts a `Conditions` instance into a `Comment` object.
+    ///
+    /// The `Comment` includes a string representation of the condition and its associated values.
+    fn to_comment(&self, comment_type: CommentType) -> Comment {
+        match comment_type {
+            CommentType::Simple => Comment {
+                string: self.to_string(),
+                values: vec![],
+                details: vec![],
+            },
+            CommentType::ShowValues => match &self {
+                Conditions::LoopCondition {
+                    loop_type,
+                    collection,
+                    element,
+                    index,
+                    condition,
+                } => {
+                    let cond_comment = condition.to_comment(comment_type);
+                    let comment = self.to_string();
+                    let bound_idents =
+                        [get_pat_idents(element), index.iter().cloned().collect()].concat();
+                    let filtered_values =
+                        filter_out_streams_with_idents(&cond_comment.values, &bound_idents)
+                            .into_iter()
+                            .cloned()
+                            .collect();
+                    let mut details =
+                        filter_out_details_with_idents(cond_comment.details, &bound_idents);
+                    if let LoopType::ForEachConsecutive = loop_type {
+                        let first_violation = consecutive_pairs_call(
+                            collection,
+                            element,
+                            index,
+                            quote! {position},
+                            quote! {!(#condition)},
+                        );
+                        details.insert(
+                            0,
+                            (
+                                "first violating index".to_owned(),
+                                quote! {#first_violation.map(|__rusty_index| __rusty_index + 1)},
+                            ),
+                        );
+                    }
+                    Comment {
+                        string: comment,
+                        values: [vec![collection.to_token_stream()], filtered_values].concat(),
+                        details,
+                    }
+                }
+                Conditions::CompoundCondition {
+                    left_condition,
+                    join: _,
+                    right_condition,
+                } => {
+                    let left_comment = left_condition.to_comment(comment_type);
+                    let right_comment = right_condition.to_comment(comment_type);
+                    let comment = self.to_string();
+                    let left_value = left_comment.values;
+                    let right_value = right_comment.values;
+                    Comment {
+                        string: comment,
+                        values: [left_value, right_value].concat(),
+                        details: [left_comment.details, right_comment.details].concat(),
+                    }
+                }
+                Conditions::CollectionCondition(condition) => condition.to_comment(comment_type),
+                Conditions::FieldsCondition(condition) => condition.to_comment(comment_type),
+                Conditions::Condition(condition) => condition.to_comment(comment_type),
+            },
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_str;
+
+    #[test]
+    fn test_parse_condition() {
+        let input = "x greater than 5";
+        let condition = syn::parse_str::<Condition>(input).unwrap();
+        assert_eq!(condition.left.to_token_stream().to_string(), "x");
+        assert_eq!(condition.symbol.to_token_stream().to_string(), ">");
+        assert_eq!(condition.right.to_token_stream().to_string(), "5");
+    }
+
+    #[test]
+    fn test_parse_conditions() {
+        let input = "x greater than 5 and y less than 10";
+        let conditions = syn::parse_str::<Conditions>(input).unwrap();
+        match conditions {
+            Conditions::CompoundCondition {
+                left_condition,
+                join,
+                right_condition,
+            } => {
+                assert_eq!(left_condition.to_string(), "x greater than 5");
+                assert_eq!(join.to_string(), "and");
+                assert_eq!(right_condition.to_string(), "y less than 10");
+            }
+            _ => panic!("Expected CompoundCondition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_loop_condition_for_each() {
+        let input = "for each item in items, item greater than 5";
+        let conditions = syn::parse_str::<Conditions>(input).unwrap();
+        match conditions {
+            Conditions::LoopCondition {
+                loop_type,
+                collection,
+                element,
+                index,
+                condition,
+            } => {
+                assert_eq!(loop_type.to_string(), "for each");
+                assert_eq!(collection.to_token_stream().to_string(), "items");
+                assert_eq!(element.to_token_stream().to_string(), "item");
+                assert!(index.is_none());
+                assert_eq!(condition.to_string(), "item greater than 5");
+            }
+            _ => panic!("Expected LoopCondition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_loop_condition_for_any() {
+        let input = "for any item in items, item greater than 5";
+        let conditions = syn::parse_str::<Conditions>(input).unwrap();
+        match conditions {
+            Conditions::LoopCondition {
+                loop_type,
+                collection,
+                element,
+                index,
+                condition,
+            } => {
+                assert_eq!(loop_type.to_string(), "for any");
+                assert_eq!(collection.to_token_stream().to_string(), "items");
+                assert_eq!(element.to_token_stream().to_string(), "item");
+                assert!(index.is_none());
+                assert_eq!(condition.to_string(), "item greater than 5");
+            }
+            _ => panic!("Expected LoopCondition"),
+        }
+    }
+
+    #[test]
+    fn test_to_tokens_condition() {
+        let input = "x greater than 5";
+        let condition = parse_str::<Conditions>(input).unwrap();
+        assert_eq!(
+            condition.to_token_stream().to_string(),
+            parse_str::<TS>("(x > 5)").unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_to_tokens_conditions() {
+        let input = "x greater than 5 and y less than 10";
+        let conditions = parse_str::<Conditions>(input).unwrap();
+        assert_eq!(
+            conditions.to_token_stream().to_string(),
+            parse_str::<TS>("((x > 5) && (y < 10))")
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_to_tokens_loop_condition_for_each() {
+        let input = "for each item in items, item greater than 5";
+        let conditions = parse_str::<Conditions>(input).unwrap();
+        assert_eq!(
+            conditions.to_token_stream().to_string(),
+            parse_str::<TS>("(items).into_iter().all(| item| (item > 5))")
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_to_tokens_loop_condition_for_any() {
+        let input = "for any item in items, item greater than 5";
+        let conditions = parse_str::<Conditions>(input).unwrap();
+        assert_eq!(
+            conditions.to_token_stream().to_string(),
+            parse_str::<TS>("(items).into_iter().any(| item| (item > 5))")
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_loop_condition_with_pattern_and_index() {
+        let input = "for each (k, v) at index i in &map, *v greater than i";
+        let conditions = parse_str::<Conditions>(input).unwrap();
+        match conditions {
+            Conditions::LoopCondition { element, index, .. } => {
+                assert_eq!(
+                    element.to_token_stream().to_string(),
+                    str_to_tokens("(k, v)")
+                );
+                assert_eq!(index.unwrap().to_string(), "i");
+            }
+            _ => panic!("Expected LoopCondition"),
+        }
+    }
+
+    #[test]
+    fn test_to_tokens_loop_condition_with_index() {
+        let input = "for each item at index i in items, item greater than i";
+        let conditions = parse_str::<Conditions>(input).unwrap();
+        assert_eq!(
+            conditions.to_token_stream().to_string(),
+            str_to_tokens("(items).into_iter().enumerate().all(|(i, item)| (item > i))")
+        );
+        assert_eq!(
+            conditions.to_string(),
+            "for each item at index i in items, item greater than i"
+        );
+    }
+
+    #[test]
+    fn test_loop_comment_filters_pattern_idents() {
+        let input = "for each (k, v) at index i in &map, *v greater than limit and k not equal i";
+        let conditions = parse_str::<Conditions>(input).unwrap();
+        let values: Vec<String> = conditions
+            .to_comment(CommentType::ShowValues)
+            .values
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(values, vec![str_to_tokens("&map"), str_to_tokens("limit")]);
+    }
+
+    fn str_to_tokens(code: &str) -> String {
+        parse_str::<TS>(code).unwrap().to_string()
+    }
+}
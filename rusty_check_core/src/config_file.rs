@@ -0,0 +1,788 @@
+//! Reading of `rusty_check.toml` config files and their discovery from a crate directory.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt, fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use super::{
+    configure::{
+        is_valid_pass_rate, parse_cfg_option, parse_duration, CommentType, Config, ConfigOption,
+        ConfigOptionName,
+    },
+    contract::ViolationMode,
+    lint::LintLevel,
+};
+use serde::Deserialize;
+use syn::{parse::Parser, Ident, LitInt};
+use toml::Spanned;
+
+/// Represents the contents of a TOML config file.
+///
+/// Every field is optional and corresponds to a [`ConfigOption`]:
+/// - `moduleName`: The name of the generated test module.
+/// - `createModule`: Whether the generated tests are wrapped in a module.
+/// - `unstable`: Whether failing checks only print a message.
+/// - `unstableReport`: The JSON-lines file failed unstable checks are recorded in,
+///   relative to the crate directory.
+/// - `resultReport`: The JSON-lines file the results of cases are recorded in,
+///   relative to the crate directory. Records are appended, the file is only
+///   cleared by `cargo rusty-check test`.
+/// - `cfg`: The cfg predicate the tests are compiled under.
+/// - `comment`: The comment type, `simple` or `show values`.
+/// - `lint`: The lint level, `allow`, `warn` or `deny`.
+/// - `violation`: How `require!` and `ensure!` report violations, `error` or `panic`.
+/// - `profile`: The name of the profile selected by default.
+/// - `tags`: The tags of the cases, such as `["slow", "db"]`.
+/// - `timeout`: The time a case may run, such as `"500ms"` or `"2s"`. The case runs on a
+///   worker thread that is left running when it times out. With `retries`, every attempt
+///   gets its own timeout.
+/// - `retries`: How many times a failed case is run again.
+/// - `backoff`: The wait before the first retry, doubled for every next one.
+/// - `minPassRate`: The share of attempts that must pass, between `0.0` and `1.0`.
+/// - `warmup`: How many times a bench runs before it is measured.
+/// - `iterations`: How many times a bench is measured, at least one.
+/// - `profiles`: Named bundles of the options above, selected with `profile = <name>`,
+///   defined in `[profiles.<name>]` tables.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub(crate) struct ConfigFile {
+    module_name: Option<Spanned<String>>,
+    create_module: Option<bool>,
+    unstable: Option<bool>,
+    unstable_report: Option<String>,
+    result_report: Option<String>,
+    cfg: Option<Spanned<String>>,
+    comment: Option<CommentType>,
+    lint: Option<LintLevel>,
+    violation: Option<ViolationMode>,
+    profile: Option<Spanned<String>>,
+    tags: Option<Vec<Spanned<String>>>,
+    timeout: Option<Spanned<String>>,
+    retries: Option<u32>,
+    backoff: Option<Spanned<String>>,
+    min_pass_rate: Option<Spanned<f64>>,
+    warmup: Option<u32>,
+    iterations: Option<Spanned<u32>>,
+    profiles: Option<Spanned<BTreeMap<Spanned<String>, ConfigFile>>>,
+}
+
+/// Name of the config file discovered next to `Cargo.toml` files.
+pub const CONFIG_FILE_NAME: &str = "rusty_check.toml";
+
+/// Represents the parts of a `Cargo.toml` relevant to the config,
+/// the `[package.metadata.rusty_check]` and `[workspace.metadata.rusty_check]` tables.
+#[derive(Debug, Default, Deserialize)]
+struct Manifest {
+    package: Option<ManifestSection>,
+    workspace: Option<ManifestSection>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ManifestSection {
+    metadata: Option<ManifestMetadata>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ManifestMetadata {
+    rusty_check: Option<ConfigFile>,
+}
+
+/// Represents an invalid or unreadable config file.
+///
+/// A `ConfigFileError` consists of:
+/// - `path`: The path of the config file.
+/// - `line`: The line of the error, if known.
+/// - `key`: The key of the invalid option, if known.
+/// - `message`: The description of the problem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigFileError {
+    /// The path of the config file.
+    pub path: String,
+    /// The line of the error, if known.
+    pub line: Option<usize>,
+    /// The key of the invalid option, if known.
+    pub key: Option<String>,
+    /// The description of the problem.
+    pub message: String,
+}
+
+impl ConfigFileError {
+    /// Creates an error about the config file at `path`, without a known line or key.
+    pub fn new(path: &str, message: String) -> Self {
+        ConfigFileError {
+            path: path.to_owned(),
+            line: None,
+            key: None,
+            message,
+        }
+    }
+
+    /// Creates an error pointing at a byte range of the config file.
+    ///
+    /// The key is read from the line containing the range, before its `=`.
+    fn at(path: &str, contents: &str, span: Range<usize>, message: String) -> Self {
+        let start = span.start.min(contents.len());
+        let line_start = contents[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = contents[start..]
+            .find('\n')
+            .map_or(contents.len(), |i| start + i);
+        let key = contents[line_start..line_end]
+            .split('=')
+            .next()
+            .map(|key| key.trim().trim_matches('"').to_owned())
+            .filter(|key| !key.is_empty() && !key.starts_with('['));
+        ConfigFileError {
+            path: path.to_owned(),
+            line: Some(contents[..start].matches('\n').count() + 1),
+            key,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ConfigFileError {
+    /// Formats a `ConfigFileError` as a human-readable string,
+    /// such as ``in `rusty.toml` at line 1, key `unstable`: expected a boolean``.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "in `{}`", self.path)?;
+        if let Some(line) = self.line {
+            write!(f, " at line {}", line)?;
+        }
+        if let Some(key) = &self.key {
+            write!(f, ", key `{}`", key)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Error for ConfigFileError {}
+
+impl ConfigFile {
+    /// Parses and validates the contents of the config file at `path`.
+    ///
+    /// # Errors
+    /// Returns a `ConfigFileError` naming the file, the line and the key if the contents
+    /// are not valid TOML, contain an unknown key, a value of the wrong type,
+    /// a module name that is not an identifier or a cfg that is not a cfg predicate.
+    pub fn parse(path: &str, contents: &str) -> Result<Config, ConfigFileError> {
+        let file: ConfigFile = deserialize(path, contents)?;
+        file.into_config(path, contents)
+    }
+
+    /// Converts the validated options into a `Config`.
+    ///
+    /// `path` and `contents` are those of the file the options were read from,
+    /// they are used to point errors at the invalid values.
+    fn into_config(self, path: &str, contents: &str) -> Result<Config, ConfigFileError> {
+        let mut options = vec![];
+        if let Some(name) = self.module_name {
+            let ident = parse_ident(path, contents, &name)?;
+            options.push((
+                ConfigOptionName::ModuleName,
+                ConfigOption::ModuleName { name: ident },
+            ));
+        }
+        if let Some(value) = self.create_module {
+            options.push((
+                ConfigOptionName::CreateModule,
+                ConfigOption::CreateModule { value },
+            ));
+        }
+        if let Some(value) = self.unstable {
+            options.push((
+                ConfigOptionName::TestUnstable,
+                ConfigOption::TestUnstable { value },
+            ));
+        }
+        if let Some(path) = self.unstable_report {
+            options.push((
+                ConfigOptionName::UnstableReport,
+                ConfigOption::UnstableReport { path: Some(path) },
+            ));
+        }
+        if let Some(path) = self.result_report {
+            options.push((
+                ConfigOptionName::ResultReport,
+                ConfigOption::ResultReport { path: Some(path) },
+            ));
+        }
+        if let Some(cfg) = self.cfg {
+            let option = parse_cfg_option.parse_str(cfg.get_ref()).map_err(|_| {
+                ConfigFileError::at(
+                    path,
+                    contents,
+                    cfg.span(),
+                    format!("expected a cfg predicate, found {:?}", cfg.get_ref()),
+                )
+            })?;
+            options.push((ConfigOptionName::CfgFlags, option));
+        }
+        if let Some(comment_type) = self.comment {
+            options.push((
+                ConfigOptionName::CommentType,
+                ConfigOption::CommentType { comment_type },
+            ));
+        }
+        if let Some(level) = self.lint {
+            options.push((ConfigOptionName::Lint, ConfigOption::Lint { level }));
+        }
+        if let Some(mode) = self.violation {
+            options.push((
+                ConfigOptionName::Violation,
+                ConfigOption::Violation { mode },
+            ));
+        }
+        if let Some(name) = self.profile {
+            let ident = parse_ident(path, contents, &name)?;
+            options.push((
+                ConfigOptionName::Profile,
+                ConfigOption::Profile { name: Some(ident) },
+            ));
+        }
+        if let Some(tags) = self.tags {
+            let tags = tags
+                .iter()
+                .map(|tag| parse_ident(path, contents, tag))
+                .collect::<Result<_, _>>()?;
+            options.push((ConfigOptionName::Tags, ConfigOption::Tags { tags }));
+        }
+        if let Some(timeout) = self.timeout {
+            let duration = parse_duration_value(path, contents, &timeout)?;
+            options.push((
+                ConfigOptionName::Timeout,
+                ConfigOption::Timeout {
+                    duration: Some(duration),
+                },
+            ));
+        }
+        if let Some(count) = self.retries {
+            options.push((ConfigOptionName::Retries, ConfigOption::Retries { count }));
+        }
+        if let Some(backoff) = self.backoff {
+            let duration = parse_duration_value(path, contents, &backoff)?;
+            options.push((
+                ConfigOptionName::Backoff,
+                ConfigOption::Backoff {
+                    duration: Some(duration),
+                },
+            ));
+        }
+        if let Some(rate) = self.min_pass_rate {
+            if !is_valid_pass_rate(*rate.get_ref()) {
+                return Err(ConfigFileError::at(
+                    path,
+                    contents,
+                    rate.span(),
+                    format!(
+                        "expected a pass rate between 0.0 and 1.0, found {}",
+                        rate.get_ref()
+                    ),
+                ));
+            }
+            options.push((
+                ConfigOptionName::MinPassRate,
+                ConfigOption::MinPassRate {
+                    rate: Some(rate.into_inner()),
+                },
+            ));
+        }
+        if let Some(count) = self.warmup {
+            options.push((ConfigOptionName::Warmup, ConfigOption::Warmup { count }));
+        }
+        if let Some(count) = self.iterations {
+            if *count.get_ref() == 0 {
+                return Err(ConfigFileError::at(
+                    path,
+                    contents,
+                    count.span(),
+                    "expected at least one iteration, found 0".to_owned(),
+                ));
+            }
+            options.push((
+                ConfigOptionName::Iterations,
+                ConfigOption::Iterations {
+                    count: count.into_inner(),
+                },
+            ));
+        }
+        if let Some(profiles) = self.profiles {
+            let mut parsed = HashMap::new();
+            for (name, profile) in profiles.into_inner() {
+                parse_ident(path, contents, &name)?;
+                if let Some(nested) = &profile.profiles {
+                    return Err(ConfigFileError::at(
+                        path,
+                        contents,
+                        nested.span(),
+                        "profiles cannot define other profiles".to_owned(),
+                    ));
+                }
+                parsed.insert(name.into_inner(), profile.into_config(path, contents)?);
+            }
+            options.push((
+                ConfigOptionName::Profiles,
+                ConfigOption::Profiles { profiles: parsed },
+            ));
+        }
+        Ok(Config {
+            options: options.into_iter().collect(),
+        })
+    }
+}
+
+/// Parses a string value of the file at `path` as an identifier.
+fn parse_ident(
+    path: &str,
+    contents: &str,
+    value: &Spanned<String>,
+) -> Result<Ident, ConfigFileError> {
+    syn::parse_str::<Ident>(value.get_ref()).map_err(|_| {
+        ConfigFileError::at(
+            path,
+            contents,
+            value.span(),
+            format!("expected an identifier, found {:?}", value.get_ref()),
+        )
+    })
+}
+
+/// Parses a duration such as `"500ms"` or `"2s"`, pointing errors at the value.
+fn parse_duration_value(
+    path: &str,
+    contents: &str,
+    value: &Spanned<String>,
+) -> Result<Duration, ConfigFileError> {
+    syn::parse_str::<LitInt>(value.get_ref())
+        .and_then(|lit| parse_duration(&lit))
+        .map_err(|_| {
+            ConfigFileError::at(
+                path,
+                contents,
+                value.span(),
+                format!(
+                    "expected a duration such as \"500ms\" or \"2s\", found {:?}",
+                    value.get_ref()
+                ),
+            )
+        })
+}
+
+/// Deserializes TOML `contents` of the file at `path`, pointing errors at the invalid key.
+fn deserialize<'de, T: Deserialize<'de>>(
+    path: &str,
+    contents: &'de str,
+) -> Result<T, ConfigFileError> {
+    toml::from_str(contents).map_err(|e| match e.span() {
+        Some(span) => ConfigFileError::at(path, contents, span, e.message().to_owned()),
+        None => ConfigFileError::new(path, e.message().to_owned()),
+    })
+}
+
+/// Reads the file at `path`, returning `None` if it does not exist.
+fn read_optional(path: &Path) -> Result<Option<String>, ConfigFileError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(ConfigFileError::new(
+            &path.display().to_string(),
+            e.to_string(),
+        )),
+    }
+}
+
+/// Reads and validates the config file at `path`.
+///
+/// # Errors
+/// Returns a `ConfigFileError` if the file cannot be read or is invalid.
+pub fn read_config_file(path: &Path) -> Result<Config, ConfigFileError> {
+    let display = path.display().to_string();
+    let contents =
+        fs::read_to_string(path).map_err(|e| ConfigFileError::new(&display, e.to_string()))?;
+    ConfigFile::parse(&display, &contents)
+}
+
+/// The kind of `Cargo.toml` found in a directory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ManifestKind {
+    Missing,
+    Package,
+    Workspace,
+}
+
+/// Reads the `Cargo.toml` of a directory, if it has one.
+fn read_manifest(dir: &Path) -> Result<Option<(PathBuf, String, Manifest)>, ConfigFileError> {
+    let path = dir.join("Cargo.toml");
+    let Some(contents) = read_optional(&path)? else {
+        return Ok(None);
+    };
+    let manifest = deserialize(&path.display().to_string(), &contents)?;
+    Ok(Some((path, contents, manifest)))
+}
+
+/// Reads the config of a single directory, given its `Cargo.toml`.
+///
+/// Options in `rusty_check.toml` take precedence over the `rusty_check` metadata
+/// of `Cargo.toml`. Returns the config together with the files it was read from.
+fn read_dir_config(
+    dir: &Path,
+    manifest: Option<(PathBuf, String, Manifest)>,
+) -> Result<(Config, Vec<PathBuf>), ConfigFileError> {
+    let mut config = Config::new();
+    let mut files = vec![];
+    let file_path = dir.join(CONFIG_FILE_NAME);
+    if let Some(contents) = read_optional(&file_path)? {
+        config = ConfigFile::parse(&file_path.display().to_string(), &contents)?;
+        files.push(file_path);
+    }
+    if let Some((manifest_path, contents, manifest)) = manifest {
+        let display = manifest_path.display().to_string();
+        for section in [manifest.package, manifest.workspace].into_iter().flatten() {
+            if let Some(file) = section.metadata.and_then(|metadata| metadata.rusty_check) {
+                config = config.merge_with_other(&file.into_config(&display, &contents)?);
+            }
+        }
+        files.push(manifest_path);
+    }
+    Ok((config, files))
+}
+
+/// Discovers the config of the crate in `crate_dir`, together with the files it was read from.
+///
+/// Every directory from `crate_dir` up to the workspace root is searched for
+/// a `rusty_check.toml` file and a `rusty_check` metadata table in `Cargo.toml`.
+/// Configs closer to the crate take precedence over those closer to the workspace root.
+/// The search stops at the first `Cargo.toml` above the crate, if it is not the
+/// manifest of a workspace root the crate is not part of a workspace and only
+/// `crate_dir` is searched.
+///
+/// # Errors
+/// Returns a `ConfigFileError` if any discovered source is unreadable or invalid.
+pub fn discover_config(crate_dir: &Path) -> Result<(Config, Vec<PathBuf>), ConfigFileError> {
+    let mut layers = vec![];
+    for dir in crate_dir.ancestors() {
+        let manifest = read_manifest(dir)?;
+        let kind = match &manifest {
+            None => ManifestKind::Missing,
+            Some((_, _, manifest)) if manifest.workspace.is_some() => ManifestKind::Workspace,
+            Some(_) => ManifestKind::Package,
+        };
+        if kind == ManifestKind::Package && dir != crate_dir {
+            break;
+        }
+        layers.push(read_dir_config(dir, manifest)?);
+        if kind == ManifestKind::Workspace {
+            return Ok(merge_layers(layers));
+        }
+    }
+    layers.truncate(1);
+    Ok(merge_layers(layers))
+}
+
+/// Merges configs ordered from the most to the least important,
+/// collecting the files they were read from.
+fn merge_layers(layers: Vec<(Config, Vec<PathBuf>)>) -> (Config, Vec<PathBuf>) {
+    layers.into_iter().fold(
+        (Config::new(), vec![]),
+        |(config, mut files), (layer, layer_files)| {
+            files.extend(layer_files);
+            (config.merge_with_other(&layer), files)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Creates an empty temporary directory unique to the test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rusty_check_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn parse_error(contents: &str) -> ConfigFileError {
+        ConfigFile::parse("rusty.toml", contents).unwrap_err()
+    }
+
+    #[test]
+    fn test_parse_every_option() {
+        let config = ConfigFile::parse(
+            "rusty.toml",
+            r#"
+moduleName = "checks"
+createModule = false
+unstable = true
+cfg = "all(unix, feature = \"slow\")"
+comment = "show values"
+lint = "deny"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.options.len(), 6);
+        assert_eq!(config.get_module_name().to_string(), "checks");
+        assert!(!config.get_create_module());
+        assert!(config.get_unstable_test());
+        assert_eq!(
+            config.get_cfg_flags().to_string(),
+            "all (unix , feature = \"slow\")"
+        );
+        assert_eq!(config.get_comment_type(), CommentType::ShowValues);
+        assert_eq!(config.get_lint_level(), LintLevel::Deny);
+    }
+
+    #[test]
+    fn test_parse_comment_spellings() {
+        for (value, comment_type) in [
+            ("simple", CommentType::Simple),
+            ("show values", CommentType::ShowValues),
+            ("showValues", CommentType::ShowValues),
+        ] {
+            let config =
+                ConfigFile::parse("rusty.toml", &format!("comment = {:?}", value)).unwrap();
+            assert_eq!(config.get_comment_type(), comment_type);
+        }
+    }
+
+    #[test]
+    fn test_parse_wrong_type() {
+        let error = parse_error("moduleName = \"checks\"\nunstable = \"yes\"");
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.key.as_deref(), Some("unstable"));
+        assert_eq!(
+            error.to_string(),
+            "in `rusty.toml` at line 2, key `unstable`: invalid type: string \"yes\", expected a boolean"
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_key() {
+        let error = parse_error("modulName = \"checks\"");
+        assert_eq!(error.key.as_deref(), Some("modulName"));
+        assert!(error.message.starts_with("unknown field `modulName`"));
+    }
+
+    #[test]
+    fn test_parse_unknown_comment_type() {
+        let error = parse_error("comment = \"verbose\"");
+        assert_eq!(error.key.as_deref(), Some("comment"));
+        assert!(error.message.starts_with("unknown variant `verbose`"));
+    }
+
+    #[test]
+    fn test_parse_invalid_values() {
+        let error = parse_error("moduleName = \"my tests\"");
+        assert_eq!(error.key.as_deref(), Some("moduleName"));
+        assert_eq!(error.message, "expected an identifier, found \"my tests\"");
+
+        let error = parse_error("cfg = \"all(\"");
+        assert_eq!(error.key.as_deref(), Some("cfg"));
+        assert_eq!(error.message, "expected a cfg predicate, found \"all(\"");
+    }
+
+    #[test]
+    fn test_parse_invalid_toml() {
+        let error = parse_error("unstable = ");
+        assert_eq!(error.line, Some(1));
+        assert_eq!(error.key.as_deref(), Some("unstable"));
+    }
+
+    #[test]
+    fn test_discover_layers_workspace_and_crate() {
+        let root = temp_dir("discover_layers");
+        let crate_dir = root.join("crates").join("member");
+        fs::create_dir_all(&crate_dir).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/member\"]\n\n[workspace.metadata.rusty_check]\nunstable = true\nlint = \"warn\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join(CONFIG_FILE_NAME),
+            "moduleName = \"shared\"\ncomment = \"simple\"\n",
+        )
+        .unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"member\"\n\n[package.metadata.rusty_check]\nlint = \"deny\"\n",
+        )
+        .unwrap();
+        fs::write(
+            crate_dir.join(CONFIG_FILE_NAME),
+            "moduleName = \"member_tests\"\n",
+        )
+        .unwrap();
+
+        let (config, files) = discover_config(&crate_dir).unwrap();
+        assert_eq!(config.get_module_name().to_string(), "member_tests");
+        assert_eq!(config.get_lint_level(), LintLevel::Deny);
+        assert_eq!(config.get_comment_type(), CommentType::Simple);
+        assert!(config.get_unstable_test());
+        assert_eq!(
+            files,
+            vec![
+                crate_dir.join(CONFIG_FILE_NAME),
+                crate_dir.join("Cargo.toml"),
+                root.join(CONFIG_FILE_NAME),
+                root.join("Cargo.toml"),
+            ]
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_discover_without_workspace_reads_only_crate() {
+        let root = temp_dir("discover_single");
+        let crate_dir = root.join("single");
+        fs::create_dir_all(&crate_dir).unwrap();
+        fs::write(root.join(CONFIG_FILE_NAME), "unstable = true\n").unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"single\"\n",
+        )
+        .unwrap();
+
+        let (config, files) = discover_config(&crate_dir).unwrap();
+        assert!(config.options.is_empty());
+        assert_eq!(files, vec![crate_dir.join("Cargo.toml")]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_discover_stops_at_enclosing_package() {
+        let root = temp_dir("discover_enclosing");
+        let crate_dir = root.join("outer").join("inner");
+        fs::create_dir_all(&crate_dir).unwrap();
+        fs::write(root.join("Cargo.toml"), "not toml [").unwrap();
+        fs::write(
+            root.join("outer").join("Cargo.toml"),
+            "[package]\nname = \"outer\"\n",
+        )
+        .unwrap();
+        fs::write(root.join("outer").join(CONFIG_FILE_NAME), "unstable = 1\n").unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"inner\"\n",
+        )
+        .unwrap();
+
+        let (config, _) = discover_config(&crate_dir).unwrap();
+        assert!(config.options.is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_discover_reports_invalid_metadata() {
+        let root = temp_dir("discover_invalid");
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"invalid\"\n\n[package.metadata.rusty_check]\nunstable = 1\n",
+        )
+        .unwrap();
+
+        let error = discover_config(&root).unwrap_err();
+        assert!(error.path.ends_with("Cargo.toml"));
+        assert_eq!(error.line, Some(5));
+        assert_eq!(error.key.as_deref(), Some("unstable"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_parse_profiles() {
+        let config = ConfigFile::parse(
+            "rusty.toml",
+            r#"
+profile = "slow"
+
+[profiles.integration]
+cfg = "feature = \"integration\""
+unstable = true
+
+[profiles.slow]
+lint = "warn"
+"#,
+        )
+        .unwrap();
+        let profiles = config.get_profiles();
+        assert_eq!(profiles.len(), 2);
+        assert!(profiles["integration"].get_unstable_test());
+        assert_eq!(profiles["slow"].get_lint_level(), LintLevel::Warn);
+        assert_eq!(config.get_profile().unwrap().to_string(), "slow");
+        assert_eq!(
+            config
+                .get_selected_profile()
+                .unwrap()
+                .unwrap()
+                .get_lint_level(),
+            LintLevel::Warn
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_profiles() {
+        let error = parse_error("[profiles.integration]\nunstable = 1\n");
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.key.as_deref(), Some("unstable"));
+
+        let error = parse_error("[profiles.\"slow tests\"]\nunstable = true\n");
+        assert_eq!(error.line, Some(1));
+        assert_eq!(
+            error.message,
+            "expected an identifier, found \"slow tests\""
+        );
+
+        let error = parse_error("[profiles.outer.profiles.inner]\nunstable = true\n");
+        assert_eq!(error.message, "profiles cannot define other profiles");
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let config = ConfigFile::parse("rusty.toml", "tags = [\"slow\", \"db\"]\n").unwrap();
+        let tags: Vec<String> = config.get_tags().iter().map(|t| t.to_string()).collect();
+        assert_eq!(tags, vec!["slow", "db"]);
+
+        let error = parse_error("tags = [\"slow\", \"needs db\"]\n");
+        assert_eq!(error.line, Some(1));
+        assert_eq!(error.message, "expected an identifier, found \"needs db\"");
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        let config = ConfigFile::parse("rusty.toml", "timeout = \"2s\"\n").unwrap();
+        assert_eq!(config.get_timeout(), Some(Duration::from_secs(2)));
+
+        let error = parse_error("unstable = true\ntimeout = \"2 minutes\"\n");
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.key.as_deref(), Some("timeout"));
+        assert_eq!(
+            error.message,
+            "expected a duration such as \"500ms\" or \"2s\", found \"2 minutes\""
+        );
+    }
+
+    #[test]
+    fn test_parse_retries() {
+        let config = ConfigFile::parse(
+            "rusty.toml",
+            "retries = 3\nbackoff = \"100ms\"\nminPassRate = 0.5\n",
+        )
+        .unwrap();
+        assert_eq!(config.get_retries(), 3);
+        assert_eq!(config.get_backoff(), Some(Duration::from_millis(100)));
+        assert_eq!(config.get_min_pass_rate(), Some(0.5));
+
+        let error = parse_error("retries = 3\nminPassRate = 1.5\n");
+        assert_eq!(error.line, Some(2));
+        assert_eq!(
+            error.message,
+            "expected a pass rate between 0.0 and 1.0, found 1.5"
+        );
+    }
+}
@@ -0,0 +1,577 @@
+use std::{collections::HashMap, time::Duration};
+
+use super::{
+    contract::ViolationMode,
+    helpers::{closest_candidate, unknown_keyword_error},
+    keywords as kw,
+    lint::LintLevel,
+};
+use proc_macro2::TokenStream as TS;
+use quote::ToTokens;
+use serde::Deserialize;
+use syn::{braced, bracketed, parse::Parse, Ident, LitFloat, LitInt, Token};
+
+/// Represents a configuration block in the RustyCheck DSL.
+///
+/// A `Config` block is used to define configuration options for a test case.
+/// It contains:
+/// - `elements`: The token stream representing the configuration values.
+///
+/// represents grammar from this diagram:
+///
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub(crate) options: HashMap<ConfigOptionName, ConfigOption>,
+}
+macro_rules! create_cfg_getters {
+    ($name:ident,$option:ident,$field:ident,$t:ty) => {
+        #[doc = concat!("Returns the `", stringify!($option), "` option, or its default value.")]
+        pub fn $name(&self) -> $t {
+            if let Some(value) = self
+                .options
+                .get(&ConfigOptionName::$option)
+                .map(|v| match v {
+                    ConfigOption::$option { $field, .. } => $field.clone(),
+                    _ => panic!(),
+                })
+            {
+                value
+            } else {
+                Self::default()
+                    .options
+                    .get(&ConfigOptionName::$option)
+                    .map(|v| match v {
+                        ConfigOption::$option { $field, .. } => $field.clone(),
+                        _ => panic!(),
+                    })
+                    .unwrap()
+            }
+        }
+    };
+}
+impl Config {
+    /// Creates a `Config` without any option set.
+    pub fn new() -> Self {
+        Config {
+            options: HashMap::new(),
+        }
+    }
+    create_cfg_getters!(get_cfg_flags, CfgFlags, flags, TS);
+    create_cfg_getters!(get_comment_type, CommentType, comment_type, CommentType);
+    create_cfg_getters!(get_unstable_test, TestUnstable, value, bool);
+    create_cfg_getters!(get_module_name, ModuleName, name, Ident);
+    create_cfg_getters!(get_create_module, CreateModule, value, bool);
+    create_cfg_getters!(get_lint_level, Lint, level, LintLevel);
+    create_cfg_getters!(get_profile, Profile, name, Option<Ident>);
+    create_cfg_getters!(get_profiles, Profiles, profiles, HashMap<String, Config>);
+    create_cfg_getters!(get_tags, Tags, tags, Vec<Ident>);
+    create_cfg_getters!(get_timeout, Timeout, duration, Option<Duration>);
+    create_cfg_getters!(get_retries, Retries, count, u32);
+    create_cfg_getters!(get_backoff, Backoff, duration, Option<Duration>);
+    create_cfg_getters!(get_min_pass_rate, MinPassRate, rate, Option<f64>);
+    create_cfg_getters!(get_unstable_report, UnstableReport, path, Option<String>);
+    create_cfg_getters!(get_result_report, ResultReport, path, Option<String>);
+    create_cfg_getters!(get_violation_mode, Violation, mode, ViolationMode);
+    create_cfg_getters!(get_warmup, Warmup, count, u32);
+    create_cfg_getters!(get_iterations, Iterations, count, u32);
+
+    /// Adds the options of the global config `global_cfg` to the config of a case or bench.
+    ///
+    /// Settings of the selected profile override the global config,
+    /// settings of the case itself override the profile.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the selected profile is not defined.
+    pub fn apply_global(self, global_cfg: &Config) -> syn::Result<Config> {
+        let merged = self.clone().merge_with_other(global_cfg);
+        Ok(match merged.get_selected_profile()? {
+            Some(profile) => self
+                .merge_with_other(&profile)
+                .merge_with_other_and_default(global_cfg),
+            None => merged.merge_with_default(),
+        })
+    }
+
+    /// Returns the settings of the selected profile, if any.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` pointing at the profile name if no such profile is defined.
+    pub fn get_selected_profile(&self) -> syn::Result<Option<Config>> {
+        let Some(name) = self.get_profile() else {
+            return Ok(None);
+        };
+        let profiles = self.get_profiles();
+        match profiles.get(&name.to_string()) {
+            Some(profile) => Ok(Some(profile.clone())),
+            None => {
+                let mut names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                names.sort();
+                let message = match closest_candidate(&name, &names) {
+                    Some(candidate) => {
+                        format!("Unknown profile `{}`, did you mean `{}`?", name, candidate)
+                    }
+                    None if names.is_empty() => {
+                        format!("Unknown profile `{}`, no profiles are defined", name)
+                    }
+                    None => format!(
+                        "Unknown profile `{}`, expected one of {}",
+                        name,
+                        names
+                            .iter()
+                            .map(|name| format!("`{}`", name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                };
+                Err(syn::Error::new(name.span(), message))
+            }
+        }
+    }
+
+    /// Adds options of `other` that are not set in `self`.
+    ///
+    /// Profiles are merged by name, so a profile defined in both configs
+    /// keeps the options of `self` and takes the missing ones from `other`.
+    /// Tags are merged as well, so a case keeps the tags of the global config.
+    pub fn merge_with_other(self, other: &Config) -> Config {
+        let mut combined = self.options.clone();
+        for (k, v) in other.options.iter() {
+            match (combined.get_mut(k), v) {
+                (
+                    Some(ConfigOption::Profiles { profiles }),
+                    ConfigOption::Profiles {
+                        profiles: other_profiles,
+                    },
+                ) => {
+                    for (name, other_profile) in other_profiles {
+                        let profile = match profiles.remove(name) {
+                            Some(profile) => profile.merge_with_other(other_profile),
+                            None => other_profile.clone(),
+                        };
+                        profiles.insert(name.clone(), profile);
+                    }
+                }
+                (Some(ConfigOption::Tags { tags }), ConfigOption::Tags { tags: other_tags }) => {
+                    for tag in other_tags {
+                        if !tags.contains(tag) {
+                            tags.push(tag.clone());
+                        }
+                    }
+                }
+                (Some(_), _) => {}
+                (None, _) => {
+                    combined.insert(k.clone(), v.clone());
+                }
+            }
+        }
+        Self { options: combined }
+    }
+    /// Fills the options that are not set with their default values.
+    pub fn merge_with_default(self) -> Self {
+        let default = Self::default();
+        self.merge_with_other(&default)
+    }
+    /// Fills the options that are not set from `other`, then from the defaults.
+    pub fn merge_with_other_and_default(self, other: &Config) -> Config {
+        let with_other = self.merge_with_other(other);
+        with_other.merge_with_default()
+    }
+}
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            options: HashMap::from([
+                (
+                    ConfigOptionName::CommentType,
+                    ConfigOption::CommentType {
+                        comment_type: CommentType::default(),
+                    },
+                ),
+                (
+                    ConfigOptionName::ModuleName,
+                    ConfigOption::ModuleName {
+                        name: Ident::new("tests", proc_macro2::Span::call_site()),
+                    },
+                ),
+                (
+                    ConfigOptionName::CfgFlags,
+                    ConfigOption::CfgFlags { flags: TS::new() },
+                ),
+                (
+                    ConfigOptionName::TestUnstable,
+                    ConfigOption::TestUnstable { value: false },
+                ),
+                (
+                    ConfigOptionName::CreateModule,
+                    ConfigOption::CreateModule { value: true },
+                ),
+                (
+                    ConfigOptionName::Lint,
+                    ConfigOption::Lint {
+                        level: LintLevel::default(),
+                    },
+                ),
+                (
+                    ConfigOptionName::Profile,
+                    ConfigOption::Profile { name: None },
+                ),
+                (
+                    ConfigOptionName::Profiles,
+                    ConfigOption::Profiles {
+                        profiles: HashMap::new(),
+                    },
+                ),
+                (ConfigOptionName::Tags, ConfigOption::Tags { tags: vec![] }),
+                (
+                    ConfigOptionName::Timeout,
+                    ConfigOption::Timeout { duration: None },
+                ),
+                (
+                    ConfigOptionName::Retries,
+                    ConfigOption::Retries { count: 0 },
+                ),
+                (
+                    ConfigOptionName::Backoff,
+                    ConfigOption::Backoff { duration: None },
+                ),
+                (
+                    ConfigOptionName::MinPassRate,
+                    ConfigOption::MinPassRate { rate: None },
+                ),
+                (
+                    ConfigOptionName::UnstableReport,
+                    ConfigOption::UnstableReport { path: None },
+                ),
+                (
+                    ConfigOptionName::ResultReport,
+                    ConfigOption::ResultReport { path: None },
+                ),
+                (
+                    ConfigOptionName::Violation,
+                    ConfigOption::Violation {
+                        mode: ViolationMode::default(),
+                    },
+                ),
+                (ConfigOptionName::Warmup, ConfigOption::Warmup { count: 10 }),
+                (
+                    ConfigOptionName::Iterations,
+                    ConfigOption::Iterations { count: 100 },
+                ),
+            ]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Copy, Default, Deserialize)]
+pub enum CommentType {
+    #[serde(rename = "simple")]
+    Simple,
+    #[default]
+    #[serde(rename = "show values", alias = "showValues")]
+    ShowValues,
+}
+
+macro_rules! enum_with_names {
+    (
+        enum $EnumName:ident {
+            $(
+                $Variant:ident $( ( $($Field:ty),* $(,)? ) )?
+                $( { $($StructField:ident : $StructTy:ty),* $(,)? } )?
+            ),* $(,)?
+        }, $EnumWithNames:ident
+    ) => {
+        #[derive(Clone,Debug)]
+        pub enum $EnumName {
+            $(
+                $Variant $( ( $($Field),* ) )?
+                $( { $($StructField : $StructTy),* } )?
+            ),*
+        }
+
+        #[derive(Clone,Debug, PartialEq, Eq, Hash)]
+        pub enum $EnumWithNames {
+            $(
+                $Variant
+            ),*
+        }
+    };
+}
+enum_with_names!(
+    enum ConfigOption {
+        CfgFlags { flags: TS },
+        CommentType { comment_type: CommentType },
+        TestUnstable { value: bool },
+        ModuleName { name: Ident },
+        CreateModule { value: bool },
+        Lint { level: LintLevel },
+        Profile { name: Option<Ident> },
+        Profiles { profiles: HashMap<String, Config> },
+        Tags { tags: Vec<Ident> },
+        Timeout { duration: Option<Duration> },
+        Retries { count: u32 },
+        Backoff { duration: Option<Duration> },
+        MinPassRate { rate: Option<f64> },
+        UnstableReport { path: Option<String> },
+        ResultReport { path: Option<String> },
+        Violation { mode: ViolationMode },
+        Warmup { count: u32 },
+        Iterations { count: u32 },
+    },
+    ConfigOptionName
+);
+
+impl Parse for ConfigOption {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::cfg) {
+            _ = input.parse::<kw::cfg>()?;
+
+            _ = input.parse::<Token![=]>()?;
+            return parse_cfg_option(input);
+        }
+        if input.peek(kw::comment) {
+            return parse_comment_option(input);
+        }
+        if input.peek(kw::unstable) && input.peek2(kw::report) {
+            _ = input.parse::<kw::unstable>()?;
+            _ = input.parse::<kw::report>()?;
+            _ = input.parse::<Token![=]>()?;
+            let path = input.parse::<syn::LitStr>()?.value();
+            return Ok(ConfigOption::UnstableReport { path: Some(path) });
+        }
+        if input.peek(kw::result) {
+            _ = input.parse::<kw::result>()?;
+            _ = input.parse::<kw::report>()?;
+            _ = input.parse::<Token![=]>()?;
+            let path = input.parse::<syn::LitStr>()?.value();
+            return Ok(ConfigOption::ResultReport { path: Some(path) });
+        }
+        if input.peek(kw::unstable) {
+            _ = input.parse::<kw::unstable>()?;
+            _ = input.parse::<Token![=]>()?;
+            let val = input.parse::<syn::LitBool>()?.value;
+            return Ok(ConfigOption::TestUnstable { value: val });
+        }
+        if input.peek(kw::module) {
+            _ = input.parse::<kw::module>()?;
+            _ = input.parse::<kw::name>()?;
+            _ = input.parse::<Token![=]>()?;
+            let name = input.parse::<Ident>()?;
+            return Ok(ConfigOption::ModuleName { name });
+        }
+        if input.peek(kw::create) {
+            _ = input.parse::<kw::create>()?;
+            _ = input.parse::<kw::module>()?;
+            _ = input.parse::<Token![=]>()?;
+            let val = input.parse::<syn::LitBool>()?.value;
+            return Ok(ConfigOption::CreateModule { value: val });
+        }
+        if input.peek(kw::lint) {
+            return parse_lint_option(input);
+        }
+        if input.peek(kw::profile) {
+            _ = input.parse::<kw::profile>()?;
+            _ = input.parse::<Token![=]>()?;
+            let name = input.parse::<Ident>()?;
+            return Ok(ConfigOption::Profile { name: Some(name) });
+        }
+        if input.peek(kw::violation) {
+            _ = input.parse::<kw::violation>()?;
+            _ = input.parse::<Token![=]>()?;
+            let mode = input.parse::<ViolationMode>()?;
+            return Ok(ConfigOption::Violation { mode });
+        }
+        if input.peek(kw::tags) {
+            return parse_tags_option(input);
+        }
+        if input.peek(kw::timeout) {
+            _ = input.parse::<kw::timeout>()?;
+            _ = input.parse::<Token![=]>()?;
+            let duration = parse_duration(&input.parse::<LitInt>()?)?;
+            return Ok(ConfigOption::Timeout {
+                duration: Some(duration),
+            });
+        }
+        if input.peek(kw::retries) {
+            _ = input.parse::<kw::retries>()?;
+            _ = input.parse::<Token![=]>()?;
+            let count = input.parse::<LitInt>()?.base10_parse::<u32>()?;
+            return Ok(ConfigOption::Retries { count });
+        }
+        if input.peek(kw::backoff) {
+            _ = input.parse::<kw::backoff>()?;
+            _ = input.parse::<Token![=]>()?;
+            let duration = parse_duration(&input.parse::<LitInt>()?)?;
+            return Ok(ConfigOption::Backoff {
+                duration: Some(duration),
+            });
+        }
+        if input.peek(kw::warmup) {
+            _ = input.parse::<kw::warmup>()?;
+            _ = input.parse::<Token![=]>()?;
+            let count = input.parse::<LitInt>()?.base10_parse::<u32>()?;
+            return Ok(ConfigOption::Warmup { count });
+        }
+        if input.peek(kw::iterations) {
+            _ = input.parse::<kw::iterations>()?;
+            _ = input.parse::<Token![=]>()?;
+            let lit = input.parse::<LitInt>()?;
+            let count = lit.base10_parse::<u32>()?;
+            if count == 0 {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "Expected at least one iteration, found `0`",
+                ));
+            }
+            return Ok(ConfigOption::Iterations { count });
+        }
+        if input.peek(kw::min) {
+            _ = input.parse::<kw::min>()?;
+            _ = input.parse::<kw::pass>()?;
+            _ = input.parse::<kw::rate>()?;
+            _ = input.parse::<Token![=]>()?;
+            let lit = input.parse::<LitFloat>()?;
+            let rate = lit.base10_parse::<f64>()?;
+            if !is_valid_pass_rate(rate) {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!("Expected a pass rate between 0.0 and 1.0, found `{}`", lit),
+                ));
+            }
+            return Ok(ConfigOption::MinPassRate { rate: Some(rate) });
+        }
+
+        Err(unknown_keyword_error(
+            input,
+            "configuration option",
+            kw::CONFIG_OPTIONS,
+        ))
+    }
+}
+
+fn parse_comment_option(input: syn::parse::ParseStream) -> syn::Result<ConfigOption> {
+    _ = input.parse::<kw::comment>()?;
+    _ = input.parse::<Token![=]>()?;
+    if input.peek(kw::simple) {
+        _ = input.parse::<kw::simple>()?;
+        return Ok(ConfigOption::CommentType {
+            comment_type: CommentType::Simple,
+        });
+    }
+    if input.peek(kw::show) && input.peek2(kw::values) {
+        _ = input.parse::<kw::show>()?;
+        _ = input.parse::<kw::values>()?;
+        return Ok(ConfigOption::CommentType {
+            comment_type: CommentType::ShowValues,
+        });
+    }
+    Err(unknown_keyword_error(
+        input,
+        "comment type",
+        kw::COMMENT_TYPES,
+    ))
+}
+
+fn parse_lint_option(input: syn::parse::ParseStream) -> syn::Result<ConfigOption> {
+    _ = input.parse::<kw::lint>()?;
+    _ = input.parse::<Token![=]>()?;
+    let level = input.parse::<LintLevel>()?;
+    Ok(ConfigOption::Lint { level })
+}
+
+fn parse_tags_option(input: syn::parse::ParseStream) -> syn::Result<ConfigOption> {
+    _ = input.parse::<kw::tags>()?;
+    _ = input.parse::<Token![=]>()?;
+    let content;
+    bracketed!(content in input);
+    let tags = content.parse_terminated(Ident::parse, Token![,])?;
+    Ok(ConfigOption::Tags {
+        tags: tags.into_iter().collect(),
+    })
+}
+
+/// Parses a duration written as an integer with a unit suffix, such as `500ms` or `2s`.
+///
+/// # Errors
+/// Returns a `syn::Error` if the suffix is not `ms` or `s`.
+pub fn parse_duration(lit: &LitInt) -> syn::Result<Duration> {
+    let value = lit.base10_parse::<u64>()?;
+    match lit.suffix() {
+        "ms" => Ok(Duration::from_millis(value)),
+        "s" => Ok(Duration::from_secs(value)),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            format!(
+                "Expected a duration such as `{0}ms` or `{0}s`, found `{1}`",
+                value, lit
+            ),
+        )),
+    }
+}
+
+/// Checks if a minimal pass rate lies between `0.0` and `1.0`.
+pub fn is_valid_pass_rate(rate: f64) -> bool {
+    (0.0..=1.0).contains(&rate)
+}
+
+pub fn parse_cfg_option(input: syn::parse::ParseStream) -> syn::Result<ConfigOption> {
+    if input.peek(syn::LitBool) {
+        Ok(ConfigOption::CfgFlags {
+            flags: input.parse::<syn::LitBool>()?.to_token_stream(),
+        })
+    } else {
+        Ok(ConfigOption::CfgFlags {
+            flags: input.parse::<syn::Meta>()?.to_token_stream(),
+        })
+    }
+}
+
+impl Parse for Config {
+    /// Parses a `Config` block from the input stream.
+    ///
+    /// # Parameters
+    /// - `input`: The parse stream to read from.
+    ///
+    /// # Returns
+    /// A parsed `Config` instance containing the `cfg` keyword and the configuration values.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the input cannot be parsed as a valid `Config` block.
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        _ = input.parse::<kw::cfg>()?;
+        if input.peek(Token![=]) {
+            _ = input.parse::<Token![=]>()?;
+            let cfg_option = parse_cfg_option(input)?;
+            Ok(Config {
+                options: HashMap::from([(ConfigOptionName::CfgFlags, cfg_option)]),
+            })
+        } else {
+            let cfg;
+            braced!(cfg in input);
+            let options = cfg.parse_terminated(ConfigOption::parse, Token![,])?;
+            let map = options
+                .into_iter()
+                .map(|opt| match opt {
+                    ConfigOption::CfgFlags { .. } => (ConfigOptionName::CfgFlags, opt),
+                    ConfigOption::CommentType { .. } => (ConfigOptionName::CommentType, opt),
+                    ConfigOption::TestUnstable { .. } => (ConfigOptionName::TestUnstable, opt),
+                    ConfigOption::ModuleName { .. } => (ConfigOptionName::ModuleName, opt),
+                    ConfigOption::CreateModule { .. } => (ConfigOptionName::CreateModule, opt),
+                    ConfigOption::Lint { .. } => (ConfigOptionName::Lint, opt),
+                    ConfigOption::Profile { .. } => (ConfigOptionName::Profile, opt),
+                    ConfigOption::Profiles { .. } => (ConfigOptionName::Profiles, opt),
+                    ConfigOption::Tags { .. } => (ConfigOptionName::Tags, opt),
+                    ConfigOption::Timeout { .. } => (ConfigOptionName::Timeout, opt),
+                    ConfigOption::Retries { .. } => (ConfigOptionName::Retries, opt),
+                    ConfigOption::Backoff { .. } => (ConfigOptionName::Backoff, opt),
+                    ConfigOption::MinPassRate { .. } => (ConfigOptionName::MinPassRate, opt),
+                    ConfigOption::UnstableReport { .. } => (ConfigOptionName::UnstableReport, opt),
+                    ConfigOption::ResultReport { .. } => (ConfigOptionName::ResultReport, opt),
+                    ConfigOption::Violation { .. } => (ConfigOptionName::Violation, opt),
+                    ConfigOption::Warmup { .. } => (ConfigOptionName::Warmup, opt),
+                    ConfigOption::Iterations { .. } => (ConfigOptionName::Iterations, opt),
+                })
+                .collect();
+            Ok(Config { options: map })
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use super::{
+    conditions::Conditions,
+    configure::{CommentType, Config},
+    helpers::{unknown_keyword_error, ToComment},
+    keywords as kw,
+};
+use proc_macro2::TokenStream as TS;
+use quote::{quote, ToTokens};
+use serde::Deserialize;
+use syn::{
+    parse::{Parse, ParseStream},
+    token, Token,
+};
+
+/// Represents how `require!` and `ensure!` report violated conditions.
+///
+/// Variants:
+/// - `Error`: The enclosing function returns `Err` with a `RustyCheckViolation`.
+/// - `Panic`: The violation panics, like a failed `check!`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViolationMode {
+    #[default]
+    Error,
+    Panic,
+}
+
+impl Parse for ViolationMode {
+    /// Parses a `ViolationMode` from the input stream.
+    ///
+    /// Recognizes the following keywords:
+    /// - `error` -> `ViolationMode::Error`
+    /// - `panic` -> `ViolationMode::Panic`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::error) {
+            _ = input.parse::<kw::error>()?;
+            Ok(ViolationMode::Error)
+        } else if input.peek(kw::panic) {
+            _ = input.parse::<kw::panic>()?;
+            Ok(ViolationMode::Panic)
+        } else {
+            Err(unknown_keyword_error(
+                input,
+                "violation mode",
+                kw::VIOLATION_MODES,
+            ))
+        }
+    }
+}
+
+/// Represents the kind of a contract check.
+///
+/// Variants:
+/// - `Require`: A precondition, checked by `require!`.
+/// - `Ensure`: A postcondition, checked by `ensure!`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContractKind {
+    /// A precondition, checked by `require!`.
+    Require,
+    /// A postcondition, checked by `ensure!`.
+    Ensure,
+}
+
+/// Represents a contract check in production code, such as
+/// `require!(cfg { violation = panic }, a less than b)`.
+///
+/// A `Contract` consists of:
+/// - `kind`: Whether the conditions are pre- or postconditions.
+/// - `config`: The options of an optional leading `cfg` block.
+/// - `conditions`: The conditions to be checked.
+#[derive(Clone, Debug)]
+pub struct Contract {
+    kind: ContractKind,
+    config: Config,
+    conditions: Conditions,
+}
+
+impl Contract {
+    /// Parses a `Contract` of the given kind from the input stream.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the input is not an optional `cfg` block
+    /// followed by a comma and conditions.
+    pub fn parse(input: ParseStream, kind: ContractKind) -> syn::Result<Self> {
+        let config = if input.peek(kw::cfg) && input.peek2(token::Brace) {
+            let config = input.parse::<Config>()?;
+            _ = input.parse::<Token![,]>()?;
+            config
+        } else {
+            Config::new()
+        };
+        let conditions = input.parse::<Conditions>()?;
+        Ok(Contract {
+            kind,
+            config,
+            conditions,
+        })
+    }
+
+    /// Adds options from config files, options of the `cfg` block take precedence.
+    pub fn apply_config_file(self, config: &Config) -> Self {
+        Contract {
+            config: self.config.merge_with_other_and_default(config),
+            ..self
+        }
+    }
+}
+
+impl ToTokens for Contract {
+    /// Generates a statement checking the conditions.
+    ///
+    /// If they do not hold, a `RustyCheckViolation` naming the conditions and
+    /// holding the debug representations of their values is either returned
+    /// as the error of the enclosing function, converted with `From`, or panicked with.
+    fn to_tokens(&self, tokens: &mut TS) {
+        let conditions = &self.conditions;
+        let comment = conditions.to_comment(CommentType::ShowValues);
+        let message = match self.kind {
+            ContractKind::Require => format!("Precondition failed: {}", comment.string),
+            ContractKind::Ensure => format!("Postcondition failed: {}", comment.string),
+        };
+        let names = comment
+            .values
+            .iter()
+            .map(|value| value.to_string())
+            .chain(comment.details.iter().map(|(label, _)| label.to_owned()));
+        let values = comment
+            .values
+            .iter()
+            .chain(comment.details.iter().map(|(_, detail)| detail));
+        let report = match self.config.get_violation_mode() {
+            ViolationMode::Error => quote! {
+                return ::core::result::Result::Err(::core::convert::From::from(__rusty_violation));
+            },
+            ViolationMode::Panic => quote! {
+                panic!("{}", __rusty_violation);
+            },
+        };
+        let slots = conditions.unordered_slots();
+        tokens.extend(quote! {
+            #slots
+            if !#conditions {
+                let __rusty_violation = ::rusty_check_runtime::RustyCheckViolation::new(
+                    #message,
+                    ::std::vec![#((::std::string::String::from(#names), ::std::format!("{:?}", #values))),*],
+                );
+                #report
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse::Parser;
+
+    fn contract(input: &str, kind: ContractKind) -> syn::Result<Contract> {
+        (|input: ParseStream| Contract::parse(input, kind)).parse_str(input)
+    }
+
+    #[test]
+    fn test_parse_violation_mode() {
+        assert_eq!(
+            syn::parse_str::<ViolationMode>("panic").unwrap(),
+            ViolationMode::Panic
+        );
+        assert_eq!(
+            syn::parse_str::<ViolationMode>("eror")
+                .unwrap_err()
+                .to_string(),
+            "Unknown violation mode `eror`, did you mean `error`?"
+        );
+    }
+
+    #[test]
+    fn test_contract_returns_error() {
+        let tokens = contract("a less than b", ContractKind::Require)
+            .unwrap()
+            .apply_config_file(&Config::new())
+            .to_token_stream()
+            .to_string();
+        assert!(tokens.contains("\"Precondition failed: a less than b\""));
+        assert!(tokens.contains("return :: core :: result :: Result :: Err"));
+    }
+
+    #[test]
+    fn test_contract_panics() {
+        let tokens = contract("cfg { violation = panic }, x equal 1", ContractKind::Ensure)
+            .unwrap()
+            .apply_config_file(&Config::new())
+            .to_token_stream()
+            .to_string();
+        assert!(tokens.contains("\"Postcondition failed: x equal 1\""));
+        assert!(tokens.contains("panic !"));
+        assert!(!tokens.contains("return"));
+    }
+}
@@ -1,11 +1,11 @@
 use std::marker::PhantomData;
 
-use super::keywords as kw;
+use super::{helpers::get_pat_idents, keywords as kw};
 use proc_macro2::TokenStream as TS;
 
 use quote::{quote, ToTokens};
 
-use syn::{braced, parse::Parse, Expr, Token};
+use syn::{braced, parse::Parse, Expr, Ident, Token};
 
 /// Represents a block of declarations, parameterized by a keyword type `K`.
 ///
@@ -29,10 +29,16 @@ pub struct DeclarationBlock<K: Parse> {
 ///
 /// # Fields
 /// - `kw`: A phantom type to associate the assignment with the keyword type `K`.
+/// - `pattern`: The assigned identifier, with its type if annotated.
+/// - `idents`: The identifiers bound by the assignment.
+/// - `value`: The assigned expression.
 /// - `data`: The token stream representing the assignment.
 #[derive(Clone, Debug)]
 pub struct Assignment<K: Parse> {
     kw: PhantomData<K>,
+    pattern: TS,
+    idents: Vec<Ident>,
+    value: Expr,
     data: TS,
 }
 
@@ -51,19 +57,23 @@ fn parse_assignment<K: Parse>(
     input: syn::parse::ParseStream,
     assignment_kw: TS,
 ) -> syn::Result<Assignment<K>> {
-    let ident = if input.peek2(Token![:]) {
-        input.parse::<syn::PatType>()?.to_token_stream()
+    let (ident, idents) = if input.peek2(Token![:]) {
+        let pat_type = input.parse::<syn::PatType>()?;
+        (pat_type.to_token_stream(), get_pat_idents(&pat_type.pat))
     } else {
-        input.parse::<syn::Ident>()?.to_token_stream()
+        let ident = input.parse::<syn::Ident>()?;
+        (ident.to_token_stream(), vec![ident])
     };
     input.parse::<Token![=]>()?;
     let exp = input.parse::<Expr>()?;
     let code: TS = quote! {
         #assignment_kw #ident = #exp;
-    }
-    .into();
+    };
     Ok(Assignment {
         kw: PhantomData,
+        pattern: ident,
+        idents,
+        value: exp,
         data: code,
     })
 }
@@ -74,7 +84,7 @@ impl<K: Parse> ToTokens for Assignment<K> {
     /// # Parameters
     /// - `tokens`: The token stream to append to.
     fn to_tokens(&self, tokens: &mut TS) {
-        tokens.extend(self.data.clone().into_iter());
+        tokens.extend(self.data.clone());
     }
 }
 
@@ -92,9 +102,9 @@ impl Parse for Assignment<kw::given> {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         if input.peek(Token![mut]) {
             _ = input.parse::<Token![mut]>();
-            parse_assignment(input, quote! {let mut}.into())
+            parse_assignment(input, quote! {let mut})
         } else {
-            parse_assignment(input, quote! {let}.into())
+            parse_assignment(input, quote! {let})
         }
     }
 }
@@ -114,9 +124,9 @@ impl Parse for Assignment<kw::vars> {
         if input.peek(Token![mut]) {
             _ = input.parse::<Token![mut]>();
 
-            parse_assignment(input, quote! {static mut}.into())
+            parse_assignment(input, quote! {static mut})
         } else {
-            parse_assignment(input, quote! {static}.into())
+            parse_assignment(input, quote! {static})
         }
     }
 }
@@ -133,7 +143,35 @@ impl Parse for Assignment<kw::consts> {
     /// # Errors
     /// Returns a `syn::Error` if the input cannot be parsed as an assignment.
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        parse_assignment(input, quote! {const}.into())
+        parse_assignment(input, quote! {const})
+    }
+}
+
+impl<K: Parse> DeclarationBlock<K> {
+    /// Returns every identifier declared in the block, in declaration order.
+    pub fn idents(&self) -> Vec<Ident> {
+        self.assignments
+            .iter()
+            .flat_map(|a| a.idents.clone())
+            .collect()
+    }
+
+    /// Returns the assigned patterns with their values, in declaration order.
+    pub fn declarations(&self) -> Vec<(&TS, &Expr)> {
+        self.assignments
+            .iter()
+            .map(|a| (&a.pattern, &a.value))
+            .collect()
+    }
+
+    /// Returns the `let` statements generated for the assignments, in declaration order.
+    pub fn statements(&self) -> Vec<&TS> {
+        self.assignments.iter().map(|a| &a.data).collect()
+    }
+
+    /// Returns the assigned expressions, in declaration order.
+    pub fn values(&self) -> Vec<&Expr> {
+        self.assignments.iter().map(|a| &a.value).collect()
     }
 }
 
@@ -173,7 +211,7 @@ impl<K: Parse> ToTokens for DeclarationBlock<K> {
         let code = quote! {
             #(#assignments)*
         };
-        tokens.extend(code.into_iter());
+        tokens.extend(code);
     }
 }
 #[cfg(test)]
@@ -245,6 +283,16 @@ mod tests {
         assert_eq!(parsed.assignments[1].data.to_string(), "let y = 100 ;");
     }
 
+    #[test]
+    fn test_declaration_block_idents() {
+        let block: DeclarationBlock<kw::given> = parse_quote! {
+            given { x = 42, mut y: u32 = x + 1 }
+        };
+        let idents: Vec<String> = block.idents().iter().map(|i| i.to_string()).collect();
+        assert_eq!(idents, vec!["x", "y"]);
+        assert_eq!(block.values()[1].to_token_stream().to_string(), "x + 1");
+    }
+
     #[test]
     fn test_parse_declaration_block_vars() {
         let input: TokenStream = quote! {
@@ -0,0 +1,236 @@
+use crate::helpers::{escape_braces, get_idents, Comment, ToComment};
+
+use super::{
+    condition::{Condition, Symbol},
+    configure::CommentType,
+    format, keywords as kw,
+};
+use proc_macro2::TokenStream as TS;
+use quote::{quote, ToTokens};
+use std::fmt;
+use syn::{braced, parse::Parse, parse_quote, punctuated::Punctuated, Token};
+
+/// Represents a condition over several fields of a single value.
+///
+/// A `FieldsCondition` consists of:
+/// - `value`: The expression whose fields are checked.
+/// - `fields`: The conditions checked, each with a path relative to `value` on its left-hand side.
+#[derive(Clone, Debug)]
+pub struct FieldsCondition {
+    pub value: syn::Expr,
+    pub fields: Vec<Condition>,
+}
+
+/// Checks if the expression is a path relative to the checked value,
+/// such as `name`, `0`, `inner.id` or `name.len()`.
+fn is_field_path(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Path(path) => path.path.get_ident().is_some(),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(_),
+            ..
+        }) => true,
+        syn::Expr::Field(field) => is_field_path(&field.base),
+        syn::Expr::MethodCall(call) => is_field_path(&call.receiver),
+        syn::Expr::Index(index) => is_field_path(&index.expr),
+        _ => false,
+    }
+}
+
+impl FieldsCondition {
+    /// Returns the field condition with its left-hand side resolved against the checked value.
+    fn resolve(&self, field: &Condition) -> Condition {
+        let value = &self.value;
+        let left = &field.left;
+        Condition {
+            left: parse_quote! {(#value).#left},
+            ..field.clone()
+        }
+    }
+
+    /// Returns the conditions of all fields resolved against the checked value.
+    pub(crate) fn resolved_fields(&self) -> Vec<Condition> {
+        self.fields
+            .iter()
+            .map(|field| self.resolve(field))
+            .collect()
+    }
+
+    /// Returns the human-readable path of a field, such as `x.name`.
+    fn path(&self, field: &Condition) -> String {
+        let value = &self.value;
+        let left = &field.left;
+        format::render(quote! {#value.#left})
+    }
+
+    /// Generates an expression listing every field that does not satisfy its condition
+    /// together with its value.
+    fn mismatches(&self) -> TS {
+        let checks = self.fields.iter().map(|field| {
+            let resolved = self.resolve(field);
+            let left = &resolved.left;
+            let message = escape_braces(&self.path(field)) + "={:?}";
+            quote! {
+                if !#resolved {
+                    __rusty_mismatches.push(format!(#message, #left));
+                }
+            }
+        });
+        quote! {
+            format_args!("{}", {
+                let mut __rusty_mismatches: Vec<String> = Vec::new();
+                #(#checks)*
+                __rusty_mismatches.join(", ")
+            })
+        }
+    }
+}
+
+/// Checks if the input stream represents a `FieldsCondition`,
+/// an expression followed by `has fields`.
+pub fn is_fields_condition(input: &syn::parse::ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<syn::Expr>().is_ok() && fork.peek(kw::has) && fork.peek2(kw::fields)
+}
+
+impl Parse for FieldsCondition {
+    /// Parses a `FieldsCondition` from the input stream.
+    ///
+    /// A `FieldsCondition` consists of:
+    /// - A value expression.
+    /// - The `has fields` keywords.
+    /// - A braced, comma separated list of conditions on fields of the value.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the left-hand side of any condition is not a field path.
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let value = input.parse::<syn::Expr>()?;
+        input.parse::<kw::has>()?;
+        input.parse::<kw::fields>()?;
+        let content;
+        braced!(content in input);
+        let fields: Vec<Condition> =
+            Punctuated::<Condition, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect();
+        if let Some(field) = fields.iter().find(|field| !is_field_path(&field.left)) {
+            return Err(syn::Error::new_spanned(
+                &field.left,
+                "Expected a field of the checked value",
+            ));
+        }
+        Ok(FieldsCondition { value, fields })
+    }
+}
+
+impl ToTokens for FieldsCondition {
+    /// Converts a `FieldsCondition` into its token representation.
+    ///
+    /// The generated expression is `true` when every field satisfies its condition.
+    fn to_tokens(&self, tokens: &mut TS) {
+        let conditions = self.fields.iter().map(|field| self.resolve(field));
+        tokens.extend(quote! {
+            ( true #(&& #conditions)* )
+        });
+    }
+}
+
+impl fmt::Display for FieldsCondition {
+    /// Formats a `FieldsCondition` as a human-readable string.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| field.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "{} has fields {{ {} }}",
+            self.value.to_token_stream(),
+            fields
+        )
+    }
+}
+
+impl ToComment for FieldsCondition {
+    /// Converts a `FieldsCondition` into a `Comment` object.
+    ///
+    /// With `CommentType::ShowValues` the `Comment` lists the path and value of every
+    /// mismatching field, together with values used on the right-hand sides
+    /// of conditions other than `matches` and `satisfies`.
+    fn to_comment(&self, comment_type: CommentType) -> Comment {
+        match comment_type {
+            CommentType::Simple => Comment {
+                string: self.to_string(),
+                values: vec![],
+                details: vec![],
+            },
+            CommentType::ShowValues => Comment {
+                string: self.to_string(),
+                values: self
+                    .fields
+                    .iter()
+                    .filter(|field| {
+                        !matches!(
+                            field.symbol.strip_negations(),
+                            (_, Symbol::Matches | Symbol::Satisfies)
+                        )
+                    })
+                    .flat_map(|field| get_idents(&field.right.to_token_stream()))
+                    .collect(),
+                details: vec![("mismatching fields".to_owned(), self.mismatches())],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_str;
+
+    #[test]
+    fn test_parse_fields_condition() {
+        let condition: FieldsCondition =
+            parse_str("x has fields { id equal 1, name starts with \"a\", }").unwrap();
+        assert_eq!(condition.value.to_token_stream().to_string(), "x");
+        assert_eq!(condition.fields.len(), 2);
+        assert_eq!(
+            condition.to_string(),
+            "x has fields { id equal 1, name starts with \"a\" }"
+        );
+    }
+
+    #[test]
+    fn test_parse_fields_condition_rejects_non_field() {
+        let result = parse_str::<FieldsCondition>("x has fields { a + b equal 1 }");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_tokens_fields_condition() {
+        let condition: FieldsCondition =
+            parse_str("x has fields { id equal 1, state matches State::Done }").unwrap();
+        assert_eq!(
+            condition.to_token_stream().to_string(),
+            parse_str::<TS>("( true && ((x).id == 1) && (matches!((x).state, State::Done)) )")
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_field_path() {
+        let condition: FieldsCondition =
+            parse_str("task has fields { inner.name.len() equal 1 }").unwrap();
+        assert_eq!(
+            condition.path(&condition.fields[0]),
+            "task.inner.name.len()"
+        );
+        let condition: FieldsCondition =
+            parse_str("pair has fields { 0 equal 1, items[1].id equal 2 }").unwrap();
+        assert_eq!(condition.path(&condition.fields[0]), "pair.0");
+        assert_eq!(condition.path(&condition.fields[1]), "pair.items[1].id");
+    }
+}
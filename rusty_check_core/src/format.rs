@@ -0,0 +1,1233 @@
+//! Formatting of `rusty_check!` invocations in Rust sources.
+
+use std::{error::Error, fmt, ops::Range, str::FromStr};
+
+use proc_macro2::{Delimiter, Ident, Spacing, TokenStream as TS, TokenTree};
+use quote::ToTokens;
+
+use super::{keywords as kw, rusty_check::RustyCheck};
+
+/// Maximum width of a formatted line, the default of `rustfmt`.
+const MAX_WIDTH: usize = 100;
+/// Indentation of one nesting level.
+const INDENT: &str = "    ";
+/// Keywords starting the blocks of `global`, `case` and `bench` blocks.
+const BLOCK_KEYWORDS: &[&str] = &[
+    "doc", "cfg", "consts", "vars", "given", "do", "measure", "check",
+];
+
+/// Words after which an operator like `-`, `*` or `|` starts an operand,
+/// besides the words of symbols.
+const PREFIX_WORDS: &[&str] = &[
+    "and", "or", "not", "by", "as", "if", "else", "match", "return", "let", "mut", "move",
+];
+
+/// Checks if the identifier is a word of a symbol or `in`, always followed by a space,
+/// so `equal ignoring order [1, 2]` is not rendered like an indexing.
+fn is_spaced_word(ident: &Ident) -> bool {
+    ident == "in"
+        || kw::SYMBOLS
+            .iter()
+            .flat_map(|symbol| symbol.split_whitespace())
+            .any(|word| ident == word)
+}
+
+/// Checks if `before` and `ident` are consecutive words of a loop, such as `for each`,
+/// so `for each (a, b) in &pairs` is not rendered like a call.
+fn is_loop_word(before: Option<&TokenTree>, ident: &Ident) -> bool {
+    let Some(TokenTree::Ident(before)) = before else {
+        return false;
+    };
+    kw::LOOPS.iter().any(|words| {
+        let words: Vec<&str> = words.split_whitespace().collect();
+        words
+            .windows(2)
+            .any(|pair| before == pair[0] && ident == pair[1])
+    })
+}
+
+/// Checks if the token can end an operand, so an operator following it is binary.
+fn is_operand(token: &TokenTree) -> bool {
+    match token {
+        TokenTree::Ident(ident) => {
+            !is_spaced_word(ident) && !PREFIX_WORDS.iter().any(|word| ident == word)
+        }
+        TokenTree::Punct(_) => false,
+        TokenTree::Group(_) | TokenTree::Literal(_) => true,
+    }
+}
+
+/// Represents the role of a token deciding the spacing around it.
+///
+/// Variants:
+/// - `Plain`: Spaced by the general rules.
+/// - `Prefix`: A unary operator, such as `-1`, `*x` or `&tasks`.
+/// - `OpenBar` and `CloseBar`: The bars around closure parameters, such as `|t| t.id`.
+/// - `OpenAngle` and `CloseAngle`: The brackets of generic arguments, such as `Vec<u8>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    Plain,
+    Prefix,
+    OpenBar,
+    CloseBar,
+    OpenAngle,
+    CloseAngle,
+}
+
+/// Assigns roles to the tokens of one level of a token stream.
+fn roles(tokens: &[TokenTree]) -> Vec<Role> {
+    let mut roles = vec![Role::Plain; tokens.len()];
+    let mut in_closure = false;
+    let mut angles = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        let TokenTree::Punct(punct) = token else {
+            continue;
+        };
+        let previous = i.checked_sub(1).map(|p| &tokens[p]);
+        let after_joint =
+            matches!(previous, Some(TokenTree::Punct(p)) if p.spacing() == Spacing::Joint);
+        // The first `&` of `&&str` is joined to the second one, both are prefixes.
+        let double_reference = punct.as_char() == '&'
+            && matches!(tokens.get(i + 1), Some(TokenTree::Punct(n)) if n.as_char() == '&');
+        let after_prefix = i > 0 && roles[i - 1] == Role::Prefix;
+        let prefix_position = !previous.is_some_and(is_operand)
+            && (after_prefix
+                || !after_joint && (punct.spacing() == Spacing::Alone || double_reference));
+        let after_colons = matches!(previous, Some(TokenTree::Punct(p)) if p.as_char() == ':')
+            && i >= 2
+            && matches!(&tokens[i - 2], TokenTree::Punct(b) if b.as_char() == ':');
+        let after_type = matches!(previous, Some(TokenTree::Ident(ident))
+            if ident.to_string().starts_with(|c: char| c.is_ascii_uppercase()));
+        roles[i] = match punct.as_char() {
+            '|' if in_closure => {
+                in_closure = false;
+                Role::CloseBar
+            }
+            '|' if prefix_position => {
+                in_closure = true;
+                Role::OpenBar
+            }
+            '-' | '*' | '&' | '!' if prefix_position => Role::Prefix,
+            '<' if after_colons || after_type => {
+                angles += 1;
+                Role::OpenAngle
+            }
+            '>' if angles > 0
+                && !matches!(previous, Some(TokenTree::Punct(p)) if p.as_char() == '-') =>
+            {
+                angles -= 1;
+                Role::CloseAngle
+            }
+            _ => Role::Plain,
+        };
+    }
+    roles
+}
+
+/// Checks if there should be no space between the token at `i` and the one before it.
+fn glued(tokens: &[TokenTree], roles: &[Role], i: usize) -> bool {
+    let (previous, next) = (&tokens[i - 1], &tokens[i]);
+    let before = i.checked_sub(2).map(|b| &tokens[b]);
+    let after_angles_call = roles[i - 1] == Role::CloseAngle
+        && matches!(next, TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis);
+    if after_angles_call
+        || matches!(roles[i - 1], Role::Prefix | Role::OpenBar | Role::OpenAngle)
+        || matches!(
+            roles[i],
+            Role::CloseBar | Role::OpenAngle | Role::CloseAngle
+        )
+    {
+        return true;
+    }
+    match (previous, next) {
+        (TokenTree::Punct(p), TokenTree::Punct(_)) if p.spacing() == Spacing::Joint => true,
+        (TokenTree::Punct(p), TokenTree::Ident(_)) if p.as_char() == '\'' => true,
+        (TokenTree::Punct(p), TokenTree::Group(g)) if p.as_char() == '#' => {
+            g.delimiter() == Delimiter::Bracket
+        }
+        (TokenTree::Punct(p), _) if p.as_char() == '.' => true,
+        (_, TokenTree::Punct(n)) if matches!(n.as_char(), '.' | ',' | ';' | '?' | ':') => true,
+        (TokenTree::Punct(p), _) if p.as_char() == ':' => {
+            matches!(before, Some(TokenTree::Punct(b)) if b.as_char() == ':')
+        }
+        (TokenTree::Punct(p), _) if p.as_char() == '!' => {
+            matches!(before, Some(TokenTree::Ident(_)))
+        }
+        (TokenTree::Ident(_), TokenTree::Punct(n)) => {
+            n.as_char() == '!' && n.spacing() == Spacing::Alone
+        }
+        (TokenTree::Ident(ident), _) if is_spaced_word(ident) || is_loop_word(before, ident) => {
+            false
+        }
+        (TokenTree::Ident(_) | TokenTree::Group(_), TokenTree::Group(g)) => {
+            matches!(g.delimiter(), Delimiter::Parenthesis | Delimiter::Bracket)
+        }
+        _ => false,
+    }
+}
+
+/// Renders tokens as readable Rust, such as `t.state equal TaskState::Pending`
+/// rather than the spaced `t . state equal TaskState :: Pending` of `to_string`.
+///
+/// The formatter lays out conditions and values with it, and specification
+/// documents and messages use it, so both show code the same way.
+pub fn render(tokens: TS) -> String {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let roles = roles(&tokens);
+    let mut text = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && !glued(&tokens, &roles, i) {
+            text.push(' ');
+        }
+        match token {
+            TokenTree::Group(group) => {
+                let inner = render(group.stream());
+                match group.delimiter() {
+                    Delimiter::Parenthesis => text.push_str(&format!("({})", inner)),
+                    Delimiter::Bracket => text.push_str(&format!("[{}]", inner)),
+                    Delimiter::Brace if inner.is_empty() => text.push_str("{}"),
+                    Delimiter::Brace => text.push_str(&format!("{{ {} }}", inner)),
+                    Delimiter::None => text.push_str(&inner),
+                }
+            }
+            token => text.push_str(&token.to_string()),
+        }
+    }
+    text
+}
+
+/// Renders a human-readable string, such as a `Conditions` rendering, as readable Rust.
+///
+/// The string is returned unchanged if it cannot be split into tokens.
+pub(crate) fn render_str(text: &str) -> String {
+    TS::from_str(text).map_or_else(|_| text.to_owned(), render)
+}
+
+/// Represents a `rusty_check!` invocation that cannot be formatted.
+///
+/// A `FormatError` consists of:
+/// - `line`: The line of the source the error was found at, starting at 1.
+/// - `message`: The description of the error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatError {
+    /// The line of the source the error was found at, starting at 1.
+    pub line: usize,
+    /// The description of the error.
+    pub message: String,
+}
+
+impl FormatError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        FormatError {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for FormatError {}
+
+/// Represents the kind of a lexed token, delimiters and punctuation hold their character.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Word,
+    Literal,
+    Punct(u8),
+    Open(u8),
+    Close(u8),
+    Comment,
+}
+
+/// Represents a token of the source with its byte range and the line it starts at.
+///
+/// For an opening delimiter `close` is the index of the matching closing delimiter.
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    start: usize,
+    end: usize,
+    line: usize,
+    close: usize,
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte == b'_' || byte.is_ascii_alphanumeric() || byte >= 0x80
+}
+
+/// Returns the index after a string literal whose contents start at `i`.
+fn skip_string(bytes: &[u8], mut i: usize, line: &mut usize) -> Option<usize> {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => {
+                if bytes.get(i + 1) == Some(&b'\n') {
+                    *line += 1;
+                }
+                i += 2;
+            }
+            b'"' => return Some(i + 1),
+            byte => {
+                if byte == b'\n' {
+                    *line += 1;
+                }
+                i += 1;
+            }
+        }
+    }
+    None
+}
+
+/// Returns the index after a raw string literal whose hashes start at `i`,
+/// or `None` if there is no raw string at `i`, like in a raw identifier `r#type`.
+fn skip_raw_string(bytes: &[u8], mut i: usize, line: &mut usize) -> Option<usize> {
+    let hashes = bytes[i..].iter().take_while(|&&byte| byte == b'#').count();
+    i += hashes;
+    if bytes.get(i) != Some(&b'"') {
+        return None;
+    }
+    i += 1;
+    while i < bytes.len() {
+        let closing = bytes.get(i + 1..i + 1 + hashes);
+        if bytes[i] == b'"' && closing.is_some_and(|closing| closing.iter().all(|&b| b == b'#')) {
+            return Some(i + 1 + hashes);
+        }
+        if bytes[i] == b'\n' {
+            *line += 1;
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Splits Rust source into tokens, keeping comments and matching delimiters.
+///
+/// # Errors
+/// Returns a `FormatError` if a delimiter is unmatched or a literal or comment is unterminated.
+fn tokenize(source: &str) -> Result<Vec<Token>, FormatError> {
+    let bytes = source.as_bytes();
+    let mut tokens: Vec<Token> = vec![];
+    let mut open: Vec<usize> = vec![];
+    let mut line = 1;
+    let mut i = 0;
+    while i < bytes.len() {
+        let (start, start_line) = (i, line);
+        let unterminated =
+            |what: &str| FormatError::new(start_line, format!("Unterminated {}", what));
+        let kind = match bytes[i] {
+            b'\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            byte if byte.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                Kind::Comment
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let mut depth = 0;
+                loop {
+                    match bytes.get(i..i + 2) {
+                        None => return Err(unterminated("block comment")),
+                        Some(b"/*") => {
+                            depth += 1;
+                            i += 2;
+                        }
+                        Some(b"*/") => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        Some(pair) => {
+                            if pair[0] == b'\n' {
+                                line += 1;
+                            }
+                            i += 1;
+                        }
+                    }
+                }
+                Kind::Comment
+            }
+            b'"' => {
+                i = skip_string(bytes, i + 1, &mut line).ok_or_else(|| unterminated("string"))?;
+                Kind::Literal
+            }
+            b'\'' => {
+                let next = source[i + 1..].chars().next().map_or(0, char::len_utf8);
+                if bytes.get(i + 1) == Some(&b'\\') {
+                    i += 3;
+                    while i < bytes.len() && bytes[i] != b'\'' {
+                        i += 1;
+                    }
+                    i += 1;
+                    Kind::Literal
+                } else if bytes.get(i + 1 + next) == Some(&b'\'') {
+                    i += 2 + next;
+                    Kind::Literal
+                } else {
+                    i += 1;
+                    while i < bytes.len() && is_word_byte(bytes[i]) {
+                        i += 1;
+                    }
+                    Kind::Word
+                }
+            }
+            byte if is_word_byte(byte) => {
+                while i < bytes.len() && is_word_byte(bytes[i]) {
+                    i += 1;
+                }
+                let word = &source[start..i];
+                if matches!(word, "r" | "br" | "cr") && matches!(bytes.get(i), Some(b'"' | b'#')) {
+                    match skip_raw_string(bytes, i, &mut line) {
+                        Some(end) => {
+                            i = end;
+                            Kind::Literal
+                        }
+                        None => {
+                            i += 1;
+                            while i < bytes.len() && is_word_byte(bytes[i]) {
+                                i += 1;
+                            }
+                            Kind::Word
+                        }
+                    }
+                } else if matches!(word, "b" | "c") && bytes.get(i) == Some(&b'"') {
+                    i = skip_string(bytes, i + 1, &mut line)
+                        .ok_or_else(|| unterminated("string"))?;
+                    Kind::Literal
+                } else if word == "b" && bytes.get(i) == Some(&b'\'') {
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != b'\'' {
+                        i += if bytes[i] == b'\\' { 2 } else { 1 };
+                    }
+                    i += 1;
+                    Kind::Literal
+                } else {
+                    Kind::Word
+                }
+            }
+            byte @ (b'(' | b'[' | b'{') => {
+                open.push(tokens.len());
+                i += 1;
+                Kind::Open(byte)
+            }
+            byte @ (b')' | b']' | b'}') => {
+                let expected = match byte {
+                    b')' => b'(',
+                    b']' => b'[',
+                    _ => b'{',
+                };
+                match open.pop() {
+                    Some(index) if tokens[index].kind == Kind::Open(expected) => {
+                        tokens[index].close = tokens.len();
+                    }
+                    _ => {
+                        return Err(FormatError::new(
+                            line,
+                            format!("Unmatched `{}`", byte as char),
+                        ))
+                    }
+                }
+                i += 1;
+                Kind::Close(byte)
+            }
+            byte => {
+                i += 1;
+                Kind::Punct(byte)
+            }
+        };
+        tokens.push(Token {
+            kind,
+            start,
+            end: i.min(bytes.len()),
+            line: start_line,
+            close: 0,
+        });
+    }
+    match open.pop() {
+        Some(index) => Err(FormatError::new(
+            tokens[index].line,
+            format!(
+                "Unclosed `{}`",
+                &source[tokens[index].start..tokens[index].end]
+            ),
+        )),
+        None => Ok(tokens),
+    }
+}
+
+/// Represents a part of the output, like an entry of a `given` block or a whole `case`,
+/// with the comments written on the lines before it and after it on its last line.
+struct Unit {
+    code: Range<usize>,
+    leading: Vec<usize>,
+    trailing: Vec<usize>,
+}
+
+/// Lays out a `rusty_check!` invocation of a source file.
+///
+/// Lines are indented relative to `indent`, the indentation of the invocation.
+struct Formatter<'a> {
+    source: &'a str,
+    tokens: &'a [Token],
+    indent: &'a str,
+}
+
+impl Formatter<'_> {
+    fn indent(&self, depth: usize) -> String {
+        self.indent.to_owned() + &INDENT.repeat(depth)
+    }
+
+    fn text(&self, i: usize) -> &str {
+        &self.source[self.tokens[i].start..self.tokens[i].end]
+    }
+
+    fn is_word(&self, i: usize, word: &str) -> bool {
+        self.tokens[i].kind == Kind::Word && self.text(i) == word
+    }
+
+    /// Checks if the token at `i`, one of the `code` tokens, is a word of a symbol,
+    /// such as the `or` of `equal or less than`.
+    fn in_symbol(&self, code: &[usize], i: usize) -> bool {
+        let Some(position) = code.iter().position(|&c| c == i) else {
+            return false;
+        };
+        kw::SYMBOLS.iter().any(|symbol| {
+            let words: Vec<&str> = symbol.split_whitespace().collect();
+            (0..words.len()).any(|k| {
+                position >= k
+                    && code.len() >= position - k + words.len()
+                    && words
+                        .iter()
+                        .zip(&code[position - k..])
+                        .all(|(word, &c)| self.is_word(c, word))
+            })
+        })
+    }
+
+    /// Checks if the token at `i` starts a `global`, `case` or `bench` block.
+    fn is_dsl_item(&self, i: usize) -> bool {
+        kw::ITEMS.iter().any(|word| self.is_word(i, word))
+    }
+
+    /// Returns the index after the token at `i`, skipping the contents of a group.
+    fn skip(&self, i: usize) -> usize {
+        match self.tokens[i].kind {
+            Kind::Open(_) => self.tokens[i].close + 1,
+            _ => i + 1,
+        }
+    }
+
+    /// Returns the indices of the tokens of `range` outside nested groups, without comments.
+    fn code(&self, range: Range<usize>) -> Vec<usize> {
+        let mut code = vec![];
+        let mut i = range.start;
+        while i < range.end {
+            if self.tokens[i].kind != Kind::Comment {
+                code.push(i);
+            }
+            i = self.skip(i);
+        }
+        code
+    }
+
+    /// Returns the indices of all comments of `range`, including those in nested groups.
+    fn comments(&self, range: Range<usize>) -> Vec<usize> {
+        range
+            .filter(|&i| self.tokens[i].kind == Kind::Comment)
+            .collect()
+    }
+
+    /// Returns the line the last token of `range` ends at.
+    fn end_line(&self, range: &Range<usize>) -> usize {
+        let last = &self.tokens[range.end - 1];
+        last.line + self.source[last.start..last.end].matches('\n').count()
+    }
+
+    /// Returns the width of the whitespace before the token at `i` on its line.
+    fn column(&self, i: usize) -> usize {
+        let start = self.tokens[i].start;
+        let line_start = self.source[..start].rfind('\n').map_or(0, |n| n + 1);
+        start - line_start
+    }
+
+    /// Renders the tokens of `range` on a single line, dropping their comments.
+    fn flat(&self, range: Range<usize>) -> String {
+        if range.is_empty() {
+            return String::new();
+        }
+        let start = self.tokens[range.start].start;
+        let mut text = self.source[start..self.tokens[range.end - 1].end].to_owned();
+        for i in self.comments(range) {
+            let token = &self.tokens[i];
+            let blank = " ".repeat(token.end - token.start);
+            text.replace_range(token.start - start..token.end - start, &blank);
+        }
+        render_str(&text)
+    }
+
+    /// Returns the lines of the tokens of `range` as written, re-indented to `depth`.
+    ///
+    /// Lines inside multi-line literals are kept unchanged.
+    fn verbatim(&self, range: Range<usize>, depth: usize) -> Vec<String> {
+        if range.is_empty() {
+            return vec![];
+        }
+        let start = self.tokens[range.start].start;
+        let text = &self.source[start..self.tokens[range.end - 1].end];
+        let base = self.column(range.start);
+        let first_line = self.tokens[range.start].line;
+        let in_literal: Vec<usize> = range
+            .clone()
+            .filter(|&i| self.tokens[i].kind == Kind::Literal)
+            .flat_map(|i| {
+                let line = self.tokens[i].line;
+                let lines = self.text(i).matches('\n').count();
+                line + 1..=line + lines
+            })
+            .collect();
+        text.split('\n')
+            .enumerate()
+            .map(|(n, line)| {
+                if in_literal.contains(&(first_line + n)) {
+                    return line.to_owned();
+                }
+                let line = line.trim_end();
+                let stripped = if n == 0 {
+                    line
+                } else {
+                    let width = line.len() - line.trim_start().len();
+                    &line[width.min(base)..]
+                };
+                if stripped.is_empty() {
+                    String::new()
+                } else {
+                    self.indent(depth) + stripped
+                }
+            })
+            .collect()
+    }
+
+    /// Attaches the comments of `range` lying between `units` to them.
+    ///
+    /// A comment on the line the previous unit or separator ends at trails the unit,
+    /// other comments lead the next unit. Comments after the last unit are returned separately.
+    fn attach(&self, range: Range<usize>, units: Vec<Range<usize>>) -> (Vec<Unit>, Vec<usize>) {
+        let mut attached: Vec<Unit> = vec![];
+        let mut pending = vec![];
+        let mut last_line = 0;
+        let mut units = units.into_iter().peekable();
+        let mut i = range.start;
+        while i < range.end {
+            if let Some(code) = units.next_if(|code| code.start == i) {
+                last_line = self.end_line(&code);
+                i = code.end;
+                attached.push(Unit {
+                    code,
+                    leading: std::mem::take(&mut pending),
+                    trailing: vec![],
+                });
+                continue;
+            }
+            let token = &self.tokens[i];
+            if token.kind != Kind::Comment {
+                last_line = token.line;
+            } else if token.line == last_line && pending.is_empty() && !attached.is_empty() {
+                attached.last_mut().unwrap().trailing.push(i);
+            } else {
+                pending.push(i);
+            }
+            i = self.skip(i);
+        }
+        (attached, pending)
+    }
+
+    /// Writes the lines of comments, each starting on its own line.
+    fn write_comments(&self, comments: &[usize], depth: usize, lines: &mut Vec<String>) {
+        for &comment in comments {
+            lines.extend(self.verbatim(comment..comment + 1, depth));
+        }
+    }
+
+    /// Writes a unit, its leading comments, its code produced by `write` and its trailing comments.
+    fn write_unit(
+        &self,
+        unit: &Unit,
+        depth: usize,
+        lines: &mut Vec<String>,
+        write: impl FnOnce(&mut Vec<String>),
+    ) {
+        self.write_comments(&unit.leading, depth, lines);
+        write(lines);
+        for &comment in &unit.trailing {
+            let text = self.text(comment);
+            match lines.last_mut() {
+                Some(line) if !text.contains('\n') => *line += &format!(" {}", text),
+                _ => self.write_comments(&[comment], depth, lines),
+            }
+        }
+    }
+
+    /// Writes a unit rendered on one line, comments inside it are moved before it.
+    fn write_line(&self, unit: &Unit, suffix: &str, depth: usize, lines: &mut Vec<String>) {
+        let line = self.indent(depth) + &self.flat(unit.code.clone()) + suffix;
+        self.write_unit(unit, depth, lines, |lines| {
+            self.write_comments(&self.comments(unit.code.clone()), depth, lines);
+            lines.push(line);
+        });
+    }
+
+    /// Splits the code of `range` at top-level tokens for which `split` is true.
+    ///
+    /// With `keep` the splitting token starts the next part, otherwise it is dropped.
+    fn split(
+        &self,
+        range: Range<usize>,
+        keep: bool,
+        split: impl Fn(usize) -> bool,
+    ) -> Vec<Range<usize>> {
+        let mut parts = vec![];
+        let mut start: Option<usize> = None;
+        let mut end = range.start;
+        for i in self.code(range) {
+            if split(i) {
+                if let Some(start) = start.take() {
+                    parts.push(start..end);
+                }
+                if keep {
+                    start = Some(i);
+                    end = self.skip(i);
+                }
+                continue;
+            }
+            start.get_or_insert(i);
+            end = self.skip(i);
+        }
+        if let Some(start) = start {
+            parts.push(start..end);
+        }
+        parts
+    }
+
+    /// Lays out the items of an invocation: Rust items, the `global` block, cases and benches.
+    fn items(&self, range: Range<usize>, depth: usize) -> Vec<String> {
+        let code = self.code(range.clone());
+        let mut units = vec![];
+        let mut n = 0;
+        while n < code.len() {
+            let start = code[n];
+            let end = if self.is_dsl_item(start) {
+                code[n..]
+                    .iter()
+                    .find(|&&i| self.tokens[i].kind == Kind::Open(b'{'))
+                    .map_or(range.end, |&i| self.skip(i))
+            } else {
+                self.item_end(&code[n..], range.end)
+            };
+            units.push(start..end);
+            while n < code.len() && code[n] < end {
+                n += 1;
+            }
+        }
+        let (units, dangling) = self.attach(range, units);
+        let mut lines = vec![];
+        for (n, unit) in units.iter().enumerate() {
+            let is_use = |unit: &Unit| self.is_word(unit.code.start, "use");
+            if n > 0 && !(is_use(unit) && is_use(&units[n - 1])) {
+                lines.push(String::new());
+            }
+            let start = unit.code.start;
+            if self.is_dsl_item(start) {
+                self.write_unit(unit, depth, &mut lines, |lines| {
+                    self.block(unit.code.clone(), depth, lines)
+                });
+            } else {
+                self.write_unit(unit, depth, &mut lines, |lines| {
+                    lines.extend(self.verbatim(unit.code.clone(), depth))
+                });
+            }
+        }
+        self.write_comments(&dangling, depth, &mut lines);
+        lines
+    }
+
+    /// Returns the index after the Rust item starting with the top-level tokens `code`.
+    ///
+    /// An item ends with a `;`, or with a brace group not following a `=`
+    /// like the body of a function. The item also ends before a `global`, `case` or `bench` block.
+    fn item_end(&self, code: &[usize], end: usize) -> usize {
+        let mut after_eq = false;
+        for (n, &i) in code.iter().enumerate() {
+            match self.tokens[i].kind {
+                _ if n > 0 && self.is_dsl_item(i) => {
+                    return i;
+                }
+                Kind::Punct(b';') => return i + 1,
+                Kind::Punct(b'=') => after_eq = true,
+                Kind::Open(b'{') if !after_eq => {
+                    return match code.get(n + 1) {
+                        Some(&next) if self.tokens[next].kind == Kind::Punct(b';') => next + 1,
+                        _ => self.skip(i),
+                    };
+                }
+                _ => {}
+            }
+        }
+        end
+    }
+
+    /// Lays out a block like `case name { ... }`, `given { ... }` or `cfg = true`.
+    fn block(&self, range: Range<usize>, depth: usize, lines: &mut Vec<String>) {
+        let code = self.code(range.clone());
+        let last = *code.last().unwrap();
+        if self.tokens[last].kind != Kind::Open(b'{') {
+            let line = self.indent(depth) + &self.flat(range);
+            lines.push(line);
+            return;
+        }
+        self.write_comments(&self.comments(range.start..last), depth, lines);
+        let header = self.flat(range.start..last);
+        let body = last + 1..self.tokens[last].close;
+        let mut body_lines = vec![];
+        match self.text(range.start) {
+            "global" | "case" | "bench" => {
+                let blocks = self.split(body.clone(), true, |i| {
+                    BLOCK_KEYWORDS.iter().any(|word| self.is_word(i, word))
+                });
+                let (units, dangling) = self.attach(body, blocks);
+                for unit in &units {
+                    self.write_unit(unit, depth + 1, &mut body_lines, |lines| {
+                        self.block(unit.code.clone(), depth + 1, lines)
+                    });
+                }
+                self.write_comments(&dangling, depth + 1, &mut body_lines);
+            }
+            "do" | "measure" => body_lines = self.verbatim(body, depth + 1),
+            "check" => self.conditions(body, depth + 1, &mut body_lines),
+            _ => {
+                let entries = self.split(body.clone(), false, |i| {
+                    self.tokens[i].kind == Kind::Punct(b',')
+                });
+                let (units, dangling) = self.attach(body, entries);
+                for unit in &units {
+                    self.write_line(unit, ",", depth + 1, &mut body_lines);
+                }
+                self.write_comments(&dangling, depth + 1, &mut body_lines);
+            }
+        }
+        let indent = self.indent(depth);
+        if body_lines.is_empty() {
+            lines.push(format!("{}{} {{}}", indent, header));
+        } else {
+            lines.push(format!("{}{} {{", indent, header));
+            lines.extend(body_lines);
+            lines.push(format!("{}}}", indent));
+        }
+    }
+
+    /// Lays out the conditions of a `check` block on one line, or with every
+    /// `and` and `or` starting a line if they are too long or contain comments.
+    fn conditions(&self, range: Range<usize>, depth: usize, lines: &mut Vec<String>) {
+        if self.code(range.clone()).is_empty() {
+            self.write_comments(&self.comments(range), depth, lines);
+            return;
+        }
+        let line = self.indent(depth) + &self.flat(range.clone());
+        if line.len() <= MAX_WIDTH && self.comments(range.clone()).is_empty() {
+            lines.push(line);
+            return;
+        }
+        let code = self.code(range.clone());
+        let parts = self.split(range.clone(), true, |i| {
+            (self.is_word(i, "and") || self.is_word(i, "or")) && !self.in_symbol(&code, i)
+        });
+        let (units, dangling) = self.attach(range, parts);
+        for unit in &units {
+            self.write_condition(unit, "", depth, lines);
+        }
+        self.write_comments(&dangling, depth, lines);
+    }
+
+    /// Writes a condition on one line, or if it is too long and checks fields,
+    /// every field condition on its own line between the braces.
+    fn write_condition(&self, unit: &Unit, suffix: &str, depth: usize, lines: &mut Vec<String>) {
+        let code = self.code(unit.code.clone());
+        let fields = code
+            .windows(2)
+            .find(|pair| {
+                self.is_word(pair[0], "fields") && self.tokens[pair[1]].kind == Kind::Open(b'{')
+            })
+            .map(|pair| pair[1]);
+        let line = self.indent(depth) + &self.flat(unit.code.clone()) + suffix;
+        let Some(open) = fields.filter(|_| line.len() > MAX_WIDTH) else {
+            self.write_line(unit, suffix, depth, lines);
+            return;
+        };
+        let close = self.tokens[open].close;
+        self.write_unit(unit, depth, lines, |lines| {
+            let head = unit.code.start..open;
+            self.write_comments(&self.comments(head.clone()), depth, lines);
+            lines.push(self.indent(depth) + &self.flat(head) + " {");
+            let parts = self.split(open + 1..close, false, |i| {
+                self.tokens[i].kind == Kind::Punct(b',')
+            });
+            let (fields, dangling) = self.attach(open + 1..close, parts);
+            for field in &fields {
+                self.write_condition(field, ",", depth + 1, lines);
+            }
+            self.write_comments(&dangling, depth + 1, lines);
+            let tail = close + 1..unit.code.end;
+            self.write_comments(&self.comments(tail.clone()), depth, lines);
+            let tail = match self.flat(tail) {
+                tail if tail.is_empty() => tail,
+                tail => format!(" {}", tail),
+            };
+            lines.push(format!("{}}}{}{}", self.indent(depth), tail, suffix));
+        });
+    }
+}
+
+/// Returns the code generated from the body of an invocation, to check
+/// formatting did not change it.
+fn generated(body: &str) -> syn::Result<String> {
+    Ok(syn::parse_str::<RustyCheck>(body)?
+        .to_token_stream()
+        .to_string())
+}
+
+/// Formats every `rusty_check!` invocation in Rust source code, leaving other code untouched.
+///
+/// Blocks are indented by four spaces and every entry of `cfg`, `given`,
+/// `consts` and `vars` blocks is written on its own line followed by a comma.
+/// Conditions and values are spaced like `x equal or less than -1`, the
+/// code of `do` blocks and Rust items is kept as written and only re-indented.
+/// Comments are preserved, those inside an entry are moved before it.
+///
+/// # Errors
+/// Returns a `FormatError` if the source cannot be split into tokens
+/// or an invocation cannot be parsed.
+pub fn format_source(source: &str) -> Result<String, FormatError> {
+    let tokens = tokenize(source)?;
+    let mut formatter = Formatter {
+        source,
+        tokens: &tokens,
+        indent: "",
+    };
+    let mut formatted = String::new();
+    let mut copied = 0;
+    let mut i = 0;
+    while i + 2 < tokens.len() {
+        let is_invocation = formatter.is_word(i, "rusty_check")
+            && tokens[i + 1].kind == Kind::Punct(b'!')
+            && tokens[i + 2].kind == Kind::Open(b'{');
+        if !is_invocation {
+            i += 1;
+            continue;
+        }
+        let (open, close) = (i + 2, tokens[i + 2].close);
+        let line = tokens[i].line;
+        let body = &source[tokens[open].end..tokens[close].start];
+        let expected =
+            generated(body).map_err(|error| FormatError::new(line, error.to_string()))?;
+        let line_start = source[..tokens[i].start].rfind('\n').map_or(0, |n| n + 1);
+        let indent = &source[line_start..line_start + formatter.column(i)];
+        formatter.indent = &indent[..indent.len() - indent.trim_start().len()];
+        let lines = formatter.items(open + 1..close, 1);
+        let mut new_body = String::new();
+        if !lines.is_empty() {
+            new_body += "\n";
+            for line in &lines {
+                new_body += line;
+                new_body += "\n";
+            }
+            new_body += formatter.indent;
+        }
+        if generated(&new_body).ok().as_ref() != Some(&expected) {
+            return Err(FormatError::new(
+                line,
+                "Formatting would change the generated code, the invocation is left unformatted",
+            ));
+        }
+        formatted += &source[copied..tokens[i + 1].end];
+        formatted += " {";
+        formatted += &new_body;
+        copied = tokens[close].start;
+        i = close + 1;
+    }
+    formatted += &source[copied..];
+    Ok(formatted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render_str("for each t in & tasks , t . state equal TaskState :: Pending"),
+            "for each t in &tasks, t.state equal TaskState::Pending"
+        );
+        assert_eq!(
+            render_str("Task { id : 1 , description : \"demo\" . to_string () , }"),
+            "Task { id: 1, description: \"demo\".to_string(), }"
+        );
+        assert_eq!(
+            render_str("vec ! [1 , 2] . len () == 2 && ! done"),
+            "vec![1, 2].len() == 2 && !done"
+        );
+        assert_eq!(render_str("a != b"), "a != b");
+        assert_eq!(
+            render_str("v equal ignoring order [2 , 1] and v [0] equal (1)"),
+            "v equal ignoring order [2, 1] and v[0] equal (1)"
+        );
+    }
+
+    #[test]
+    fn test_render_operators() {
+        assert_eq!(
+            render_str("x equal - 1 and a - b equal * y"),
+            "x equal -1 and a - b equal *y"
+        );
+        assert_eq!(
+            render_str("tasks is sorted by | t | t . id"),
+            "tasks is sorted by |t| t.id"
+        );
+        assert_eq!(
+            render_str("v : HashMap < String , Vec < u8 >> = HashMap :: new ()"),
+            "v: HashMap<String, Vec<u8>> = HashMap::new()"
+        );
+        assert_eq!(
+            render_str("s . iter () . map (| x | x * 2) . collect :: < Vec < _ >> ()"),
+            "s.iter().map(|x| x * 2).collect::<Vec<_>>()"
+        );
+        assert_eq!(render_str("a && ! b || c & d"), "a && !b || c & d");
+        assert_eq!(render_str("x : & 'static str"), "x: &'static str");
+        assert_eq!(
+            render_str("v is sorted by |w: &&str| w.len() descending"),
+            "v is sorted by |w: &&str| w.len() descending"
+        );
+        assert_eq!(render_str("a && &b"), "a && &b");
+    }
+
+    #[test]
+    fn test_render_loops() {
+        assert_eq!(
+            render_str("for each (a , b) in & pairs , * a less than * b"),
+            "for each (a, b) in &pairs, *a less than *b"
+        );
+        assert_eq!(
+            render_str("for each consecutive (a , b) in & v , * a less than * b"),
+            "for each consecutive (a, b) in &v, *a less than *b"
+        );
+        assert_eq!(
+            render_str("v . iter () . any (| x | * x > 0) equal true"),
+            "v.iter().any(|x| *x > 0) equal true"
+        );
+    }
+
+    #[test]
+    fn test_format_source() {
+        let source = r#"use rusty_check::rusty_check;
+
+  rusty_check!{
+ global { cfg { unstable = true , module name=checks } }
+    case  addition{
+        // Adds numbers.
+        given { a = vec![1,2],   b = - 3 }
+        do {
+            let sum = a[0] + b; // Sum.
+            let text = "x
+  y";
+        }
+        check {
+            sum equal   or less than  a [1]
+        }
+    }
+    fn helper() -> u32 {
+        1
+    }
+}
+"#;
+        let expected = r#"use rusty_check::rusty_check;
+
+  rusty_check! {
+      global {
+          cfg {
+              unstable = true,
+              module name = checks,
+          }
+      }
+
+      case addition {
+          // Adds numbers.
+          given {
+              a = vec![1, 2],
+              b = -3,
+          }
+          do {
+              let sum = a[0] + b; // Sum.
+              let text = "x
+  y";
+          }
+          check {
+              sum equal or less than a[1]
+          }
+      }
+
+      fn helper() -> u32 {
+          1
+      }
+  }
+"#;
+        let formatted = format_source(source).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_format_comments() {
+        let source = "rusty_check! {
+    /* The only case. */
+    case c { cfg = true
+        given {
+            a = 1, // One.
+            // Two.
+            b = 2 /* Kept. */,
+        }
+        check { a less than b // Holds.
+            and b equal 2 }
+        // Last.
+    }
+}";
+        let expected = "rusty_check! {
+    /* The only case. */
+    case c {
+        cfg = true
+        given {
+            a = 1, // One.
+            // Two.
+            b = 2, /* Kept. */
+        }
+        check {
+            a less than b // Holds.
+            and b equal 2
+        }
+        // Last.
+    }
+}";
+        assert_eq!(format_source(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_bench() {
+        let source = "rusty_check! {
+bench sum { cfg { iterations = 10 } measure {
+    (0..10).sum::<u32>()
+  } check { elapsed less than   5ms } }
+}
+";
+        let expected = "rusty_check! {
+    bench sum {
+        cfg {
+            iterations = 10,
+        }
+        measure {
+            (0..10).sum::<u32>()
+        }
+        check {
+            elapsed less than 5ms
+        }
+    }
+}
+";
+        assert_eq!(format_source(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_doc_clause() {
+        let source = "rusty_check! { case add { doc for crate :: math :: add given { a = 1 } check { add(a, 1) equal 2 } } }\n";
+        let expected = "rusty_check! {
+    case add {
+        doc for crate::math::add
+        given {
+            a = 1,
+        }
+        check {
+            add(a, 1) equal 2
+        }
+    }
+}
+";
+        assert_eq!(format_source(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_long_conditions() {
+        let condition = "first_value_with_a_long_name equal 1";
+        let source = format!(
+            "rusty_check! {{ case c {{ check {{ {0} and {0} or {0} }} }} }}",
+            condition
+        );
+        let formatted = format_source(&source).unwrap();
+        assert!(formatted.contains(&format!(
+            "\n            {0}\n            and {0}\n            or {0}\n",
+            condition
+        )));
+
+        let condition = "first_value_with_a_long_name equal or greater than second_value";
+        let source = format!(
+            "rusty_check! {{ case c {{ check {{ {0} or {0} }} }} }}",
+            condition
+        );
+        let formatted = format_source(&source).unwrap();
+        assert!(formatted.contains(&format!(
+            "\n            {0}\n            or {0}\n",
+            condition
+        )));
+    }
+
+    #[test]
+    fn test_format_long_fields() {
+        let source = "rusty_check! { case c { check { item has fields { id equal expected_id, \
+            name starts with \"a\", name.len() greater than 3, state matches State::Done } \
+            and item.name not starts with \"b\" } } }";
+        let formatted = format_source(source).unwrap();
+        assert!(formatted.contains(
+            "            item has fields {\n                id equal expected_id,\n                \
+             name starts with \"a\",\n                name.len() greater than 3,\n                \
+             state matches State::Done,\n            }\n            and item.name not starts with \"b\"\n"
+        ));
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_format_errors() {
+        assert_eq!(
+            format_source("fn main() {\n    rusty_check! { case c { check { 1 equl 1 } } }\n}")
+                .unwrap_err()
+                .to_string(),
+            "line 2: Unknown symbol `equl`, did you mean `equal`?"
+        );
+        assert_eq!(
+            format_source("fn main() {\n    let x = (1;\n}").unwrap_err(),
+            FormatError::new(3, "Unmatched `}`")
+        );
+        assert_eq!(
+            format_source("let s = r#\"}\"#;").unwrap(),
+            "let s = r#\"}\"#;"
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
-
-use super::{configure::Config, declaration_block::DeclarationBlock, keywords as kw};
+use super::{
+    configure::Config, declaration_block::DeclarationBlock, helpers::unknown_keyword_error,
+    keywords as kw,
+};
 use syn::{braced, parse::Parse};
 
 type Consts = DeclarationBlock<kw::consts>;
@@ -44,27 +45,31 @@ impl Parse for Global {
         let mut vars = None;
 
         while !content.is_empty() {
+            let span = content.span();
             if content.peek(kw::cfg) {
                 let item = content.parse::<Config>()?;
                 if config.is_some() {
-                    // return Err(syn::Error::new_spanned(item, "Duplicate `configure` block"));
-                    todo!()
+                    return Err(syn::Error::new(span, "Duplicate `cfg` block"));
                 }
                 config = Some(item);
             } else if content.peek(kw::consts) {
                 let item = content.parse::<Consts>()?;
                 if consts.is_some() {
-                    return Err(syn::Error::new_spanned(item, "Duplicate `constants` block"));
+                    return Err(syn::Error::new(span, "Duplicate `consts` block"));
                 }
                 consts = Some(item);
             } else if content.peek(kw::vars) {
                 let item = content.parse::<Vars>()?;
                 if vars.is_some() {
-                    return Err(syn::Error::new_spanned(item, "Duplicate `vars` block"));
+                    return Err(syn::Error::new(span, "Duplicate `vars` block"));
                 }
                 vars = Some(item);
             } else {
-                return Err(content.error("Expected `configure`, `constants`, or `vars` block"));
+                return Err(unknown_keyword_error(
+                    &content,
+                    "global block",
+                    kw::GLOBAL_BLOCKS,
+                ));
             }
         }
         Ok(Global {
@@ -0,0 +1,332 @@
+use proc_macro2::TokenStream as TS;
+use quote::{quote, ToTokens};
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use syn::{
+    parse::ParseStream,
+    parse2,
+    visit::{self, Visit},
+    Expr, Pat,
+};
+
+use super::configure::CommentType;
+
+#[derive(Clone)]
+pub struct Comment {
+    pub string: String,
+    pub values: Vec<TS>,
+    /// Values computed only for the failure message, shown as `label={:?}`.
+    pub details: Vec<(String, TS)>,
+}
+
+/// Escapes braces so the text is not interpreted as a format argument.
+pub fn escape_braces(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
+
+impl ToTokens for Comment {
+    fn to_tokens(&self, tokens: &mut TS) {
+        let string = escape_braces(&self.string);
+        let where_str = if self.values.is_empty() && self.details.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " where, {}",
+                self.values
+                    .iter()
+                    .map(|v| v.to_string())
+                    .chain(self.details.iter().map(|(label, _)| label.to_owned()))
+                    .map(|name| format!("{}={{:?}}", escape_braces(&name)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        let values = &self.values;
+        let details = self.details.iter().map(|(_, detail)| detail);
+        let comment = string.to_owned() + where_str.as_str();
+        tokens.extend(quote! {
+            #comment #(, #values)* #(, #details)*
+        });
+    }
+}
+
+pub trait ToComment {
+    fn to_comment(&self, comment_type: CommentType) -> Comment;
+}
+
+struct IdentSeeker {
+    idents: Vec<TS>,
+}
+
+impl<'ast> Visit<'ast> for IdentSeeker {
+    fn visit_pat(&mut self, pat: &'ast syn::Pat) {
+        if let syn::Pat::Ident(_pat_ident) = pat {
+            // Add the pattern identifier to the idents list
+            self.idents.push(quote! { #pat });
+        }
+        visit::visit_pat(self, pat);
+    }
+    fn visit_expr_path(&mut self, path: &'ast syn::ExprPath) {
+        // dbg!(path);
+        if let Some(_ident) = path.path.get_ident() {
+            // We assume that simple identifiers are variable references
+            self.idents.push(path.to_token_stream());
+        }
+    }
+
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        let ident = &local.pat;
+        // Assuming locals are always variable references
+        self.idents.push(ident.to_token_stream());
+        visit::visit_local(self, local);
+    }
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        // dbg!(call);
+        self.idents.push(call.to_token_stream());
+        // visit::visit_expr_call(self, call);
+    }
+    fn visit_expr_method_call(&mut self, method: &'ast syn::ExprMethodCall) {
+        self.idents.push(method.to_token_stream());
+    }
+}
+pub fn get_idents(expr: &TokenStream) -> Vec<TS> {
+    let expr = parse2::<Expr>(expr.clone()).expect("Expected Expression");
+    let mut visitor = IdentSeeker { idents: vec![] };
+    visitor.visit_expr(&expr);
+    visitor.idents
+}
+
+struct PatIdentSeeker {
+    idents: Vec<Ident>,
+}
+
+impl<'ast> Visit<'ast> for PatIdentSeeker {
+    fn visit_pat_ident(&mut self, pat_ident: &'ast syn::PatIdent) {
+        self.idents.push(pat_ident.ident.clone());
+        visit::visit_pat_ident(self, pat_ident);
+    }
+}
+
+/// Collects every identifier token in a stream, including those nested in groups.
+///
+/// Identifiers directly after a `.` are field or method names and are skipped.
+pub fn get_ident_tokens(stream: &TokenStream) -> Vec<Ident> {
+    let mut idents = vec![];
+    let mut after_dot = false;
+    for tree in stream.clone() {
+        match &tree {
+            TokenTree::Ident(ident) if !after_dot => idents.push(ident.clone()),
+            TokenTree::Group(group) => idents.extend(get_ident_tokens(&group.stream())),
+            _ => {}
+        }
+        after_dot = matches!(&tree, TokenTree::Punct(punct) if punct.as_char() == '.');
+    }
+    idents
+}
+
+/// Collects every identifier bound by a pattern, e.g. `k` and `v` in `(k, v)`.
+pub fn get_pat_idents(pat: &Pat) -> Vec<Ident> {
+    let mut visitor = PatIdentSeeker { idents: vec![] };
+    visitor.visit_pat(pat);
+    visitor.idents
+}
+
+struct IdentFinder<'a> {
+    targets: &'a [Ident],
+    found: bool,
+}
+
+impl<'a, 'ast> Visit<'ast> for IdentFinder<'a> {
+    fn visit_ident(&mut self, i: &'ast Ident) {
+        if self.targets.contains(i) {
+            self.found = true;
+        }
+        visit::visit_ident(self, i);
+    }
+}
+
+fn contains_any_ident(expr: &Expr, idents: &[Ident]) -> bool {
+    let mut visitor = IdentFinder {
+        targets: idents,
+        found: false,
+    };
+    visitor.visit_expr(expr);
+    visitor.found
+}
+
+pub fn filter_out_details_with_idents(
+    details: Vec<(String, TokenStream)>,
+    idents: &[Ident],
+) -> Vec<(String, TokenStream)> {
+    details
+        .into_iter()
+        .filter(|(_, ts)| !filter_out_streams_with_idents([ts], idents).is_empty())
+        .collect()
+}
+
+pub fn filter_out_streams_with_idents<'a>(
+    streams: impl IntoIterator<Item = &'a TokenStream>,
+    idents: &[Ident],
+) -> Vec<&'a TokenStream> {
+    streams
+        .into_iter()
+        .filter(|ts| {
+            match parse2::<Expr>((*ts).clone()) {
+                Ok(expr) => !contains_any_ident(&expr, idents),
+                Err(_) => true, // if not parsable, keep it
+            }
+        })
+        .collect()
+}
+
+/// Computes the number of single character edits turning `a` into `b`,
+/// counting a swap of two neighbouring characters as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+/// Finds the candidate closest to the words at the start of `words`.
+///
+/// Candidates consisting of more words are preferred, so `equal or les than`
+/// suggests `equal or less than` rather than `equal`.
+/// Returns the suggested candidate together with the number of words it replaces.
+fn suggest<'a>(words: &[Ident], candidates: &[&'a str]) -> Option<(&'a str, usize)> {
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let length = candidate.split(' ').count();
+            if length > words.len() {
+                return None;
+            }
+            let typed = words[..length]
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let distance = edit_distance(&typed, candidate);
+            (distance <= (candidate.len() / 3).max(1)).then_some((candidate, length, distance))
+        })
+        .max_by_key(|(_, length, distance)| (*length, std::cmp::Reverse(*distance)))
+        .map(|(candidate, length, _)| (*candidate, length))
+}
+
+/// Finds the candidate closest to a single word, such as a misspelled name.
+pub fn closest_candidate<'a>(word: &Ident, candidates: &[&'a str]) -> Option<&'a str> {
+    suggest(std::slice::from_ref(word), candidates).map(|(candidate, _)| candidate)
+}
+
+/// Creates an error for a misspelled keyword at the start of the input,
+/// spanning the misspelled words and suggesting the closest candidate.
+///
+/// Returns `None` if no candidate is close enough.
+pub fn misspelled_keyword_error(
+    input: ParseStream,
+    what: &str,
+    candidates: &[&str],
+) -> Option<syn::Error> {
+    let mut words = vec![];
+    let mut cursor = input.cursor();
+    while let Some((word, next)) = cursor.ident() {
+        words.push(word);
+        cursor = next;
+    }
+    suggest(&words, candidates).map(|(candidate, length)| {
+        let typed = &words[..length];
+        syn::Error::new_spanned(
+            quote! {#(#typed)*},
+            format!(
+                "Unknown {} `{}`, did you mean `{}`?",
+                what,
+                typed
+                    .iter()
+                    .map(|word| word.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                candidate
+            ),
+        )
+    })
+}
+
+/// Creates an error for an unknown keyword at the start of the input.
+///
+/// The error spans the words replaced by the closest candidate and suggests it,
+/// otherwise it points at the next token and lists every candidate.
+pub fn unknown_keyword_error(input: ParseStream, what: &str, candidates: &[&str]) -> syn::Error {
+    match misspelled_keyword_error(input, what, candidates) {
+        Some(error) => error,
+        None => input.error(format!(
+            "Unknown {}, expected one of {}",
+            what,
+            candidates
+                .iter()
+                .map(|candidate| format!("`{}`", candidate))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse::Parser;
+
+    fn error_message(code: &str, candidates: &[&str]) -> String {
+        let parser = |input: ParseStream| -> syn::Result<()> {
+            Err(unknown_keyword_error(input, "symbol", candidates))
+        };
+        parser.parse_str(code).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("equal", "equal"), 0);
+        assert_eq!(edit_distance("equl", "equal"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("gvien", "given"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_unknown_keyword_suggests_closest_candidate() {
+        let candidates = ["equal", "equal or less than", "less than"];
+        assert_eq!(
+            error_message("equal or les than 2", &candidates),
+            "Unknown symbol `equal or les than`, did you mean `equal or less than`?"
+        );
+        assert_eq!(
+            error_message("equl y", &candidates),
+            "Unknown symbol `equl`, did you mean `equal`?"
+        );
+    }
+
+    #[test]
+    fn test_unknown_keyword_lists_candidates() {
+        assert_eq!(
+            error_message("banana", &["equal", "less than"]),
+            "Unknown symbol, expected one of `equal`, `less than`"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+syn::custom_keyword!(case);
+syn::custom_keyword!(bench);
+syn::custom_keyword!(measure);
+syn::custom_keyword!(doc);
+
+// Keyword tables, used to suggest the closest keyword in parse errors.
+/// Blocks at the top level of the macro.
+pub const ITEMS: &[&str] = &["case", "bench", "global"];
+/// Blocks of a `global` block.
+pub const GLOBAL_BLOCKS: &[&str] = &["cfg", "consts", "vars"];
+/// Blocks of a `case`, in order.
+pub const CASE_BLOCKS: &[&str] = &["doc", "cfg", "given", "do", "check"];
+/// Blocks of a `bench`, in order.
+pub const BENCH_BLOCKS: &[&str] = &["cfg", "given", "measure", "check"];
+/// Loops of a `check` block.
+pub const LOOPS: &[&str] = &["for each", "for each consecutive", "for any"];
+/// Symbols of a condition, each of them can also be prefixed by `not`.
+pub const SYMBOLS: &[&str] = &[
+    "equal",
+    "equal or less than",
+    "equal or greater than",
+    "less than",
+    "greater than",
+    "equal elementwise",
+    "equal ignoring order",
+    "same elements as",
+    "starts with",
+    "matches",
+    "satisfies",
+];
+/// Options of a `cfg` block.
+pub const CONFIG_OPTIONS: &[&str] = &[
+    "cfg",
+    "comment",
+    "unstable",
+    "unstable report",
+    "result report",
+    "module name",
+    "create module",
+    "lint",
+    "profile",
+    "tags",
+    "violation",
+    "timeout",
+    "retries",
+    "backoff",
+    "min pass rate",
+    "warmup",
+    "iterations",
+];
+/// Values of the `comment` option.
+pub const COMMENT_TYPES: &[&str] = &["simple", "show values"];
+/// Values of the `lint` option.
+pub const LINT_LEVELS: &[&str] = &["allow", "warn", "deny"];
+/// Values of the `violation` option.
+pub const VIOLATION_MODES: &[&str] = &["error", "panic"];
+
+syn::custom_keyword!(given);
+// keyword do
+syn::custom_keyword!(check);
+// condition keywords
+syn::custom_keyword!(not);
+syn::custom_keyword!(equal);
+syn::custom_keyword!(less);
+syn::custom_keyword!(greater);
+syn::custom_keyword!(than);
+syn::custom_keyword!(or);
+syn::custom_keyword!(and);
+syn::custom_keyword!(elementwise);
+syn::custom_keyword!(ignoring);
+syn::custom_keyword!(order);
+syn::custom_keyword!(same);
+syn::custom_keyword!(starts);
+syn::custom_keyword!(with);
+syn::custom_keyword!(matches);
+syn::custom_keyword!(satisfies);
+// collection keywords
+syn::custom_keyword!(is);
+syn::custom_keyword!(sorted);
+syn::custom_keyword!(by);
+syn::custom_keyword!(descending);
+syn::custom_keyword!(has);
+syn::custom_keyword!(unique);
+syn::custom_keyword!(elements);
+syn::custom_keyword!(fields);
+// looping trough iterators in condition
+// keyword for
+syn::custom_keyword!(each);
+syn::custom_keyword!(any);
+syn::custom_keyword!(at);
+syn::custom_keyword!(index);
+syn::custom_keyword!(consecutive);
+
+// expression keywords
+syn::custom_keyword!(rust);
+
+syn::custom_keyword!(global);
+//cfg
+syn::custom_keyword!(cfg);
+
+syn::custom_keyword!(comment);
+syn::custom_keyword!(simple);
+syn::custom_keyword!(show);
+syn::custom_keyword!(values);
+
+syn::custom_keyword!(unstable);
+syn::custom_keyword!(report);
+syn::custom_keyword!(result);
+
+syn::custom_keyword!(module);
+syn::custom_keyword!(name);
+syn::custom_keyword!(create);
+
+syn::custom_keyword!(lint);
+syn::custom_keyword!(profile);
+syn::custom_keyword!(tags);
+syn::custom_keyword!(timeout);
+syn::custom_keyword!(retries);
+syn::custom_keyword!(backoff);
+syn::custom_keyword!(min);
+syn::custom_keyword!(pass);
+syn::custom_keyword!(rate);
+syn::custom_keyword!(warmup);
+syn::custom_keyword!(iterations);
+syn::custom_keyword!(allow);
+syn::custom_keyword!(warn);
+syn::custom_keyword!(deny);
+
+syn::custom_keyword!(violation);
+syn::custom_keyword!(error);
+syn::custom_keyword!(panic);
+//cfg
+syn::custom_keyword!(consts);
+syn::custom_keyword!(vars);
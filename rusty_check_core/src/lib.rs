@@ -0,0 +1,72 @@
+//! Parser and code generator of the RustyCheck DSL.
+//!
+//! The `rusty_check` crate is a thin proc-macro wrapper around this library.
+//! Tools working with the DSL, such as formatters, linters or documentation
+//! generators, can use it to parse invocations without going through the compiler:
+//!
+//! ```
+//! use rusty_check_core::RustyCheck;
+//!
+//! let rusty: RustyCheck = syn::parse_str(
+//!     "case addition {
+//!         given { a = 2, b = 3 }
+//!         check { a + b equal 5 }
+//!     }",
+//! )
+//! .unwrap();
+//! let cases = rusty.cases().unwrap();
+//! assert_eq!(cases[0].ident().to_string(), "addition");
+//! ```
+//!
+//! Every node of the syntax tree implements [`syn::parse::Parse`] and
+//! [`quote::ToTokens`], the latter generating the code of the macros.
+//! The main nodes are re-exported from the crate root:
+//!
+//! - [`RustyCheck`]: The input of `rusty_check!`, a list of cases, benches and Rust items.
+//! - [`Case`]: A `case` block with its `doc for` clause and `cfg`, `given`, `do` and `check` blocks.
+//! - [`Bench`]: A `bench` block with its `cfg`, `given`, `measure` and `check` blocks.
+//! - [`Check`]: A `check` block, also the input of the `check!` macro.
+//! - [`Conditions`]: Conditions joined with `and` and `or`.
+//! - [`Condition`]: A single condition, such as `x equal 1`.
+//! - [`Config`]: The options of a `cfg` block or of config files.
+//! - [`DeclarationBlock`]: A `given`, `consts` or `vars` block.
+//! - [`RustyCase`]: The input of `#[rusty_case]`, a function whose body is a case.
+//! - [`Contract`]: The input of `require!` and `ensure!`.
+//!
+//! The [`config_file`], [`format`](mod@format) and [`spec`] modules read config files,
+//! format invocations and render specification documents.
+
+#![warn(missing_docs)]
+
+mod bench;
+mod case;
+mod check;
+mod collection_condition;
+mod compute;
+mod condition;
+mod conditions;
+pub mod config_file;
+mod configure;
+mod contract;
+mod declaration_block;
+mod fields_condition;
+pub mod format;
+mod global;
+mod helpers;
+mod keywords;
+mod lint;
+mod runtime_env;
+mod rusty_case;
+mod rusty_check;
+pub mod spec;
+
+pub use bench::Bench;
+pub use case::Case;
+pub use check::Check;
+pub use condition::Condition;
+pub use conditions::Conditions;
+pub use configure::Config;
+pub use contract::{Contract, ContractKind};
+pub use declaration_block::DeclarationBlock;
+pub use rusty_case::RustyCase;
+pub use rusty_check::RustyCheck;
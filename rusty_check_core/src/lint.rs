@@ -0,0 +1,222 @@
+use super::{
+    condition::{Condition, Symbol},
+    conditions::Conditions,
+    helpers::unknown_keyword_error,
+    keywords as kw,
+};
+use proc_macro2::{Span, TokenStream as TS};
+use quote::{quote, quote_spanned, ToTokens};
+use serde::Deserialize;
+use syn::{parse::Parse, Expr, Ident};
+
+/// Represents how lint diagnostics of a case are reported.
+///
+/// Variants:
+/// - `Allow`: Diagnostics are not reported.
+/// - `Warn`: Diagnostics are reported as compiler warnings.
+/// - `Deny`: Diagnostics are reported as compiler errors.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    #[default]
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl Parse for LintLevel {
+    /// Parses a `LintLevel` from the input stream.
+    ///
+    /// Recognizes the following keywords:
+    /// - `allow` -> `LintLevel::Allow`
+    /// - `warn` -> `LintLevel::Warn`
+    /// - `deny` -> `LintLevel::Deny`
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::allow) {
+            _ = input.parse::<kw::allow>()?;
+            Ok(LintLevel::Allow)
+        } else if input.peek(kw::warn) {
+            _ = input.parse::<kw::warn>()?;
+            Ok(LintLevel::Warn)
+        } else if input.peek(kw::deny) {
+            _ = input.parse::<kw::deny>()?;
+            Ok(LintLevel::Deny)
+        } else {
+            Err(unknown_keyword_error(input, "lint level", kw::LINT_LEVELS))
+        }
+    }
+}
+
+/// Represents a diagnostic found in a test case at macro expansion time.
+///
+/// A `Lint` consists of:
+/// - `tokens`: The tokens the diagnostic points at.
+/// - `message`: The description of the problem.
+#[derive(Clone, Debug)]
+pub struct Lint {
+    tokens: TS,
+    pub message: String,
+}
+
+impl Lint {
+    pub fn new(tokens: impl ToTokens, message: String) -> Self {
+        Lint {
+            tokens: tokens.to_token_stream(),
+            message,
+        }
+    }
+
+    /// Generates a `compile_error!` spanning the tokens of the lint.
+    pub fn to_error(&self) -> TS {
+        syn::Error::new_spanned(&self.tokens, &self.message).to_compile_error()
+    }
+
+    /// Generates a statement raising a compiler warning at the start of the tokens of the lint.
+    ///
+    /// Stable Rust has no API for warnings from procedural macros, so the warning is
+    /// raised by calling a deprecated function carrying the message as its note.
+    pub fn to_warning(&self) -> TS {
+        let span = self
+            .tokens
+            .clone()
+            .into_iter()
+            .next()
+            .map_or_else(Span::call_site, |token| token.span());
+        let message = &self.message;
+        let call = quote_spanned! {span=> rusty_check_lint()};
+        quote! {
+            {
+                #[deprecated(note = #message)]
+                fn rusty_check_lint() {}
+                #call;
+            }
+        }
+    }
+}
+
+/// Reports every `given` identifier that is not among the used identifiers.
+pub fn unused_given(given: &[Ident], used: &[Ident]) -> Vec<Lint> {
+    given
+        .iter()
+        .filter(|ident| !used.contains(ident))
+        .map(|ident| {
+            Lint::new(
+                ident,
+                format!("`{}` is given but never used in `do` or `check`", ident),
+            )
+        })
+        .collect()
+}
+
+/// Checks if the expression is a literal, such as `1`, `-1` or `"text"`.
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => is_literal(expr),
+        Expr::Paren(syn::ExprParen { expr, .. }) | Expr::Group(syn::ExprGroup { expr, .. }) => {
+            is_literal(expr)
+        }
+        _ => false,
+    }
+}
+
+/// Reports a condition whose result does not depend on the tested code.
+///
+/// Such a condition either compares two literals, like `1 equal 2`,
+/// or compares an expression with itself, like `x equal x`.
+/// The right-hand sides of `matches` and `satisfies` are not values and are never reported.
+fn constant_condition(condition: &Condition) -> Option<Lint> {
+    if let (_, Symbol::Matches | Symbol::Satisfies) = condition.symbol.strip_negations() {
+        return None;
+    }
+    let (left, right) = (&condition.left, &condition.right);
+    let tokens = quote! {#left #right};
+    if is_literal(&condition.left) && is_literal(&condition.right) {
+        Some(Lint::new(
+            tokens,
+            format!("condition `{}` compares two literals", condition),
+        ))
+    } else if condition.left == condition.right {
+        Some(Lint::new(
+            tokens,
+            format!(
+                "condition `{}` compares an expression with itself",
+                condition
+            ),
+        ))
+    } else {
+        None
+    }
+}
+
+/// Reports every condition whose result does not depend on the tested code.
+pub fn constant_conditions(conditions: &Conditions) -> Vec<Lint> {
+    conditions
+        .conditions()
+        .into_iter()
+        .filter_map(constant_condition)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{parse_quote, parse_str};
+
+    #[test]
+    fn test_parse_lint_level() {
+        assert_eq!(parse_str::<LintLevel>("warn").unwrap(), LintLevel::Warn);
+        assert_eq!(parse_str::<LintLevel>("deny").unwrap(), LintLevel::Deny);
+        assert_eq!(parse_str::<LintLevel>("allow").unwrap(), LintLevel::Allow);
+        assert_eq!(
+            parse_str::<LintLevel>("wran").unwrap_err().to_string(),
+            "Unknown lint level `wran`, did you mean `warn`?"
+        );
+    }
+
+    #[test]
+    fn test_unused_given() {
+        let given: Vec<Ident> = vec![parse_quote!(x), parse_quote!(y)];
+        let used: Vec<Ident> = vec![parse_quote!(y)];
+        let lints = unused_given(&given, &used);
+        assert_eq!(lints.len(), 1);
+        assert_eq!(
+            lints[0].message,
+            "`x` is given but never used in `do` or `check`"
+        );
+    }
+
+    #[test]
+    fn test_constant_conditions() {
+        let conditions: Conditions =
+            parse_str("1 equal 2 and x equal x and x less than -1 and s matches \"a\"").unwrap();
+        let messages: Vec<String> = constant_conditions(&conditions)
+            .iter()
+            .map(|lint| lint.message.clone())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "condition `1 equal 2` compares two literals",
+                "condition `x equal x` compares an expression with itself",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_constant_conditions_in_loops() {
+        let conditions: Conditions = parse_str("for each e in v, e.id equal e.id").unwrap();
+        assert_eq!(constant_conditions(&conditions).len(), 1);
+    }
+
+    #[test]
+    fn test_lint_error_and_warning() {
+        let lint = Lint::new(quote! {x}, "message".to_owned());
+        assert!(lint.to_error().to_string().contains("compile_error"));
+        assert!(lint.to_warning().to_string().contains("deprecated"));
+    }
+}
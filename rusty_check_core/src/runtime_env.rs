@@ -0,0 +1,84 @@
+use proc_macro2::TokenStream as TS;
+use quote::quote;
+
+/// Environment variable that turns failed unstable checks back into panics when set to `fail`.
+pub const UNSTABLE_VAR: &str = "RUSTY_CHECK_UNSTABLE";
+/// Environment variable that trims failure messages to the conditions when set to `simple`.
+pub const COMMENT_VAR: &str = "RUSTY_CHECK_COMMENT";
+/// Environment variable holding a comma separated list of tags, only cases with one of them run.
+pub const ONLY_TAGS_VAR: &str = "RUSTY_CHECK_ONLY_TAGS";
+
+/// Generates an expression checking at runtime if the environment variable `var` equals `value`.
+fn env_equals(var: &str, value: &str) -> TS {
+    quote! {
+        ::std::env::var(#var).is_ok_and(|__rusty_value| __rusty_value.trim() == #value)
+    }
+}
+
+/// Generates an expression checking if failed unstable checks should fail the test.
+pub fn unstable_fails() -> TS {
+    env_equals(UNSTABLE_VAR, "fail")
+}
+
+/// Generates an expression checking if failure messages should be simple.
+pub fn simple_comment() -> TS {
+    env_equals(COMMENT_VAR, "simple")
+}
+
+/// Generates a statement returning early from the test of case `name` when its tags
+/// are filtered out.
+///
+/// The test is skipped if `RUSTY_CHECK_ONLY_TAGS` is set and none of the listed
+/// tags is among `tags`; a case without tags is skipped by any filter.
+/// The harness reports a skipped test as passed, so a skip line is written to
+/// the standard error directly, where the harness does not capture it.
+pub fn tag_filter(name: &str, tags: &[String]) -> TS {
+    quote! {
+        if let Ok(__rusty_only_tags) = ::std::env::var(#ONLY_TAGS_VAR) {
+            let __rusty_tags: &[&str] = &[#(#tags),*];
+            if !__rusty_only_tags
+                .split(',')
+                .map(str::trim)
+                .any(|__rusty_tag| __rusty_tags.contains(&__rusty_tag))
+            {
+                let _ = ::std::io::Write::write_all(
+                    &mut ::std::io::stderr(),
+                    ::std::format!(
+                        "Skipped case `{}`, none of its tags {:?} is in {}={}\n",
+                        #name,
+                        __rusty_tags,
+                        #ONLY_TAGS_VAR,
+                        __rusty_only_tags
+                    )
+                    .as_bytes(),
+                );
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_equals() {
+        assert_eq!(
+            unstable_fails().to_string(),
+            quote! {
+                ::std::env::var("RUSTY_CHECK_UNSTABLE").is_ok_and(|__rusty_value| __rusty_value.trim() == "fail")
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_tag_filter() {
+        let filter = tag_filter("query", &["db".to_owned(), "slow".to_owned()]).to_string();
+        assert!(filter.contains("\"RUSTY_CHECK_ONLY_TAGS\""));
+        assert!(filter.contains(&quote! {&["db", "slow"]}.to_string()));
+        assert!(filter.contains("Skipped case `{}`"));
+        assert!(filter.contains(":: std :: io :: stderr ()"));
+    }
+}
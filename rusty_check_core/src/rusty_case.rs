@@ -0,0 +1,262 @@
+use super::{case::Case, configure::Config, rusty_check::tag_modules};
+use proc_macro2::{Ident, TokenStream as TS};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Expr, ItemFn, Macro, Stmt};
+
+/// Represents a test case written as a function annotated with `#[rusty_case]`:
+///
+/// ```ignore
+/// #[rusty_case(unstable = true)]
+/// fn addition() {
+///     given! { a = 2, b = 3 }
+///     let sum = a + b;
+///     check! { sum equal 5 }
+/// }
+/// ```
+///
+/// The attribute arguments are the options of a `cfg` block, a leading `given!`
+/// is the `given` block, a trailing `check!` is the `check` block and the
+/// statements in between are the `do` block. The function is rewritten into
+/// a `case` and parsed as one, so only the surface syntax differs. Its doc
+/// comments and visibility are kept on the generated test function.
+#[derive(Clone, Debug)]
+pub struct RustyCase {
+    case: Case,
+}
+
+/// Returns the macro invocation of a statement if it is `name! { ... }`,
+/// or a tail expression like `name!(...)`.
+fn dsl_macro<'a>(stmt: &'a Stmt, name: &str) -> Option<&'a Macro> {
+    match stmt {
+        Stmt::Macro(stmt) if stmt.mac.path.is_ident(name) => Some(&stmt.mac),
+        Stmt::Expr(Expr::Macro(expr), None) if expr.mac.path.is_ident(name) => Some(&expr.mac),
+        _ => None,
+    }
+}
+
+/// Creates a DSL keyword located at the name of a `given!` or `check!` macro.
+fn keyword(mac: &Macro, name: &str) -> Ident {
+    Ident::new(name, mac.path.span())
+}
+
+impl RustyCase {
+    /// Parses a `#[rusty_case]` function into a case.
+    ///
+    /// # Parameters
+    /// - `args`: The arguments of the attribute, options of a `cfg` block.
+    /// - `item`: The annotated function.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the item is not a function without arguments,
+    /// `given!` is not its first statement, `check!` is not its last statement,
+    /// or the resulting case cannot be parsed.
+    pub fn parse(args: TS, item: TS) -> syn::Result<RustyCase> {
+        let item: ItemFn = syn::parse2(item)?;
+        if let Some(attr) = item.attrs.iter().find(|attr| !attr.path().is_ident("doc")) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "Attributes are not supported on `#[rusty_case]` functions, the test attributes are generated",
+            ));
+        }
+        let sig = &item.sig;
+        if !sig.inputs.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "`#[rusty_case]` functions cannot take arguments",
+            ));
+        }
+        if sig.asyncness.is_some()
+            || !sig.generics.params.is_empty()
+            || !matches!(sig.output, syn::ReturnType::Default)
+        {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "`#[rusty_case]` functions must be plain `fn name()` functions",
+            ));
+        }
+
+        let mut stmts = item.block.stmts.as_slice();
+        let given = match stmts.first().and_then(|stmt| dsl_macro(stmt, "given")) {
+            Some(mac) => {
+                stmts = &stmts[1..];
+                let kw = keyword(mac, "given");
+                let values = &mac.tokens;
+                quote! {#kw { #values }}
+            }
+            None => TS::new(),
+        };
+        let Some((last, compute)) = stmts.split_last() else {
+            return Err(syn::Error::new_spanned(
+                &sig.ident,
+                "Expected `check!` as the last statement of a `#[rusty_case]` function",
+            ));
+        };
+        let Some(check) = dsl_macro(last, "check") else {
+            return Err(syn::Error::new_spanned(
+                last,
+                "Expected `check!` as the last statement of a `#[rusty_case]` function",
+            ));
+        };
+        for stmt in compute {
+            for name in ["given", "check"] {
+                if let Some(mac) = dsl_macro(stmt, name) {
+                    let message = match name {
+                        "given" => {
+                            "`given!` must be the first statement of a `#[rusty_case]` function"
+                        }
+                        _ => "`check!` must be the last statement of a `#[rusty_case]` function",
+                    };
+                    return Err(syn::Error::new_spanned(&mac.path, message));
+                }
+            }
+        }
+        let compute = if compute.is_empty() {
+            TS::new()
+        } else {
+            quote! { do { #(#compute)* } }
+        };
+        let check_kw = keyword(check, "check");
+        let conditions = &check.tokens;
+        let check = quote! {#check_kw { #conditions }};
+        let cfg = if args.is_empty() {
+            TS::new()
+        } else {
+            quote_spanned! {args.span()=> cfg { #args }}
+        };
+        let ident = &sig.ident;
+        let case = syn::parse2::<Case>(quote! {
+            case #ident {
+                #cfg
+                #given
+                #compute
+                #check
+            }
+        })?;
+        Ok(RustyCase {
+            case: case.with_item_attrs(item.attrs, item.vis),
+        })
+    }
+
+    /// Returns the case the function was desugared into.
+    pub fn case(&self) -> &Case {
+        &self.case
+    }
+
+    /// Applies the config read from config files to the case.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if the selected profile is not defined.
+    pub fn apply_config_file(self, config: &Config) -> syn::Result<RustyCase> {
+        Ok(RustyCase {
+            case: self.case.apply_global_config(config)?,
+        })
+    }
+}
+
+impl ToTokens for RustyCase {
+    /// Generates the test function of the case, placed into modules named after its tags.
+    fn to_tokens(&self, tokens: &mut TS) {
+        tokens.extend(tag_modules(vec![(
+            self.case.tags(),
+            self.case.to_token_stream(),
+        )]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: TS, item: TS) -> syn::Result<String> {
+        RustyCase::parse(args, item)?
+            .apply_config_file(&Config::default())
+            .map(|case| case.to_token_stream().to_string())
+    }
+
+    #[test]
+    fn test_rusty_case_matches_case() {
+        let from_fn = parse(
+            quote! {comment = simple},
+            quote! {
+                fn addition() {
+                    given! { a = 2, b = 3 }
+                    let sum = a + b;
+                    check! { sum equal 5 }
+                }
+            },
+        )
+        .unwrap();
+        let case: Case = syn::parse2(quote! {
+            case addition {
+                cfg { comment = simple }
+                given { a = 2, b = 3 }
+                do { let sum = a + b; }
+                check { sum equal 5 }
+            }
+        })
+        .unwrap();
+        let case = case.apply_global_config(&Config::default()).unwrap();
+        assert_eq!(from_fn, case.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn test_rusty_case_keeps_docs_and_visibility() {
+        let tokens = parse(
+            TS::new(),
+            quote! {
+                /// Adds numbers.
+                pub fn addition() {
+                    check! { 1 equal 1 }
+                }
+            },
+        )
+        .unwrap();
+        assert!(tokens.starts_with("# [doc = r\" Adds numbers.\"] # [test] pub fn addition ()"));
+    }
+
+    #[test]
+    fn test_rusty_case_accepts_paren_macros() {
+        let with_braces = parse(
+            TS::new(),
+            quote! { fn c() { given! { a = 1 } check! { a equal 1 } } },
+        )
+        .unwrap();
+        let tail = parse(
+            TS::new(),
+            quote! { fn c() { given!(a = 1); check!(a equal 1) } },
+        )
+        .unwrap();
+        let statement = parse(
+            TS::new(),
+            quote! { fn c() { given!(a = 1); check!(a equal 1); } },
+        )
+        .unwrap();
+        assert_eq!(tail, with_braces);
+        assert_eq!(statement, with_braces);
+    }
+
+    #[test]
+    fn test_rusty_case_errors() {
+        let error = |item: TS| parse(TS::new(), item).unwrap_err().to_string();
+        assert_eq!(
+            error(quote! { fn c(x: u32) { check! { x equal 1 } } }),
+            "`#[rusty_case]` functions cannot take arguments"
+        );
+        assert_eq!(
+            error(quote! { fn c() { let x = 1; } }),
+            "Expected `check!` as the last statement of a `#[rusty_case]` function"
+        );
+        assert_eq!(
+            error(quote! { fn c() { let x = 1; given! { y = 1 } check! { x equal y } } }),
+            "`given!` must be the first statement of a `#[rusty_case]` function"
+        );
+        assert_eq!(
+            error(quote! { #[test] fn c() { check! { 1 equal 1 } } }),
+            "Attributes are not supported on `#[rusty_case]` functions, the test attributes are generated"
+        );
+        assert_eq!(
+            error(quote! { fn c() { check! { 1 equl 1 } } }),
+            "Unknown symbol `equl`, did you mean `equal`?"
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use std::collections::BTreeMap;
+
+use super::{
+    bench::Bench, case::Case, configure::Config, global::Global, helpers::misspelled_keyword_error,
+    keywords as kw, spec,
+};
+use proc_macro2::{Delimiter, TokenStream as TS, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{discouraged::Speculative, Parse},
+    Ident, Item, Token,
+};
+
+/// Represents a full `rusty_check!` macro input, consisting of:
+///
+/// 1. Optional global configuration (via `Global`)  
+/// 2. A list of `case` blocks (via [`Case`])  
+/// 3. A list of `bench` blocks (via [`Bench`])  
+/// 4. Additional Rust items that will be inserted into the generated test module.
+///
+/// This structure is parsed directly from the macro input stream using `syn`.
+#[derive(Clone, Debug)]
+pub struct RustyCheck {
+    /// Optional global configuration, starting with the `global` keyword.
+    globals: Option<Global>,
+    /// A list of `case` blocks that define individual test cases.
+    cases: Vec<Case>,
+    /// A list of `bench` blocks that define micro-benchmarks.
+    benches: Vec<Bench>,
+    /// Arbitrary Rust code items to be included in the generated test module.
+    rust_code: Vec<Item>,
+}
+
+impl RustyCheck {
+    /// Returns the config of the global `cfg` block, or an empty one.
+    pub fn get_config(&self) -> Config {
+        self.globals
+            .as_ref()
+            .map(|g| g.config.clone())
+            .unwrap_or_default()
+    }
+    fn set_config(&mut self, config: &Config) {
+        match &mut self.globals {
+            Some(globals) => {
+                let mut new_globals = globals.clone();
+                new_globals.config = new_globals.config.merge_with_other(config);
+                self.globals = Some(new_globals);
+            }
+            None => {
+                self.globals = Some(Global {
+                    config: config.clone(),
+                    consts: None,
+                    vars: None,
+                });
+            }
+        }
+    }
+    /// Renders the module and its cases as a Markdown specification document.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if a case selects an undefined profile.
+    pub fn to_spec(&self) -> syn::Result<String> {
+        let config = self.get_config();
+        let mut spec = format!("# Specification of `{}`\n\n", config.get_module_name());
+        if let Some(Global { consts, vars, .. }) = &self.globals {
+            if let Some(consts) = consts {
+                spec += &format!("Constants:\n\n{}\n", spec::declarations_spec(consts));
+            }
+            if let Some(vars) = vars {
+                spec += &format!("Variables:\n\n{}\n", spec::declarations_spec(vars));
+            }
+        }
+        let cases: Vec<String> = self.cases()?.iter().map(Case::to_spec).collect();
+        spec += &cases.join("\n");
+        Ok(spec)
+    }
+
+    /// Returns the cases with the global config applied.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if a case selects an undefined profile.
+    pub fn cases(&self) -> syn::Result<Vec<Case>> {
+        let config = self.get_config();
+        self.cases
+            .iter()
+            .map(|case| case.clone().apply_global_config(&config))
+            .collect()
+    }
+
+    /// Generates the documentation examples of the cases with a `doc for` clause,
+    /// as hidden items placed next to the test module, see [`Case::doc_item`].
+    ///
+    /// `crate_name` is the name the examples import the documented functions from.
+    ///
+    /// # Errors
+    /// Returns a `syn::Error` if a case selects an undefined profile.
+    pub fn doc_examples(&self, crate_name: &str) -> syn::Result<TS> {
+        let config = self.get_config();
+        let module_name = config.get_module_name();
+        Ok(self
+            .cases()?
+            .iter()
+            .map(|case| {
+                let ident = quote::format_ident!("__rusty_doc_{}_{}", module_name, case.ident());
+                case.doc_item(crate_name, &ident, config.get_cfg_flags())
+            })
+            .collect())
+    }
+
+    /// Merges `config` into the global config, keeping the options set in the invocation.
+    pub fn apply_config_file(self, config: &Config) -> Self {
+        let mut new = self.clone();
+        new.set_config(config);
+        new
+    }
+
+    /// Generates the Criterion functions of the benches.
+    ///
+    /// Crates are built without `cfg(test)` as dependencies of bench targets, so the
+    /// functions are not put in the test module. With `create module`, they are put in
+    /// a public module named after it with a `_criterion` suffix, together with the
+    /// global declarations. The Rust items of the invocation stay in the test module.
+    #[cfg(feature = "criterion")]
+    fn criterion(&self, config: &Config) -> TS {
+        let benches: Vec<TS> = self
+            .benches
+            .iter()
+            .filter_map(|bench| bench.clone().apply_global_config(config).ok())
+            .map(|bench| bench.criterion())
+            .collect();
+        if benches.is_empty() || !config.get_create_module() {
+            return quote! { #(#benches)* };
+        }
+        let (consts, vars) = match &self.globals {
+            Some(Global { consts, vars, .. }) => (consts, vars),
+            None => (&None, &None),
+        };
+        let mut cfg_flags = config.get_cfg_flags();
+        if !cfg_flags.is_empty() {
+            cfg_flags = quote! {#[cfg(#cfg_flags)]};
+        }
+        let module_name = quote::format_ident!("{}_criterion", config.get_module_name());
+        quote! {
+            #cfg_flags
+            #[allow(dead_code)]
+            pub mod #module_name {
+                #[allow(unused_imports)]
+                use super::*;
+                #consts
+                #vars
+                #(#benches)*
+            }
+        }
+    }
+}
+
+/// Checks if a token starts a `case` or `bench` block.
+fn is_block_start(tree: &TokenTree) -> bool {
+    matches!(tree, TokenTree::Ident(ident) if ident == "case" || ident == "bench")
+}
+
+/// Skips a block starting with a keyword, such as `case name { ... }`,
+/// so parsing can continue after an error in it.
+///
+/// Stops early before the next `case` or `bench` keyword if the block has no braces.
+fn skip_block(input: syn::parse::ParseStream) -> syn::Result<()> {
+    input.step(|cursor| {
+        let mut rest = cursor.token_tree().map_or(*cursor, |(_, next)| next);
+        while let Some((tree, next)) = rest.token_tree() {
+            match &tree {
+                _ if is_block_start(&tree) => break,
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    rest = next;
+                    break;
+                }
+                _ => rest = next,
+            }
+        }
+        Ok(((), rest))
+    })
+}
+
+/// Parses a block starting with a keyword, skipping it entirely if it is broken.
+fn parse_block<T: Parse>(input: syn::parse::ParseStream) -> syn::Result<T> {
+    let fork = input.fork();
+    match fork.parse::<T>() {
+        Ok(block) => {
+            input.advance_to(&fork);
+            Ok(block)
+        }
+        Err(error) => {
+            skip_block(input)?;
+            Err(error)
+        }
+    }
+}
+
+/// Skips tokens up to the next `case` or `bench` keyword, so parsing can continue after an error.
+fn skip_to_next_case(input: syn::parse::ParseStream) -> syn::Result<()> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        while let Some((tree, next)) = rest.token_tree() {
+            if is_block_start(&tree) {
+                break;
+            }
+            rest = next;
+        }
+        Ok(((), rest))
+    })
+}
+
+/// Parses a Rust item, suggesting a DSL keyword when the item starts with a misspelled one.
+fn parse_item(input: syn::parse::ParseStream) -> syn::Result<Item> {
+    let start = input.fork();
+    input
+        .parse::<Item>()
+        .map_err(|error| misspelled_keyword_error(&start, "item", kw::ITEMS).unwrap_or(error))
+}
+
+impl Parse for RustyCheck {
+    /// Parses the macro input into a [`RustyCheck`] structure.
+    ///
+    /// Parsing rules:
+    /// - An optional `global` block is parsed first if present.
+    /// - Subsequent `case` blocks are parsed and pushed into [`Self::cases`],
+    ///   `bench` blocks into `benches`.
+    /// - Any other Rust items between cases are stored in `rust_code`.
+    ///
+    /// # Errors
+    /// After an error parsing continues from the next `case` or `bench`,
+    /// all errors are reported together.
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut cases = Vec::new();
+        let mut benches = Vec::new();
+        let mut rust_code = Vec::new();
+        let mut errors: Vec<syn::Error> = Vec::new();
+        let mut record = |result: syn::Result<()>| {
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        };
+        while !input.is_empty() && input.peek(Token![use]) {
+            rust_code.push(input.parse::<Item>()?);
+        }
+        let mut globals = None;
+        if input.peek(kw::global) {
+            record(parse_block::<Global>(input).map(|global| globals = Some(global)));
+        }
+
+        while !input.is_empty() {
+            if input.peek(kw::case) {
+                // Parse a test case block
+                record(parse_block::<Case>(input).map(|case| cases.push(case)));
+            } else if input.peek(kw::bench) {
+                record(parse_block::<Bench>(input).map(|bench| benches.push(bench)));
+            } else {
+                // Collect Rust items until we hit the next `case` or `bench` keyword
+                while !input.is_empty() && !input.peek(kw::case) && !input.peek(kw::bench) {
+                    let item = parse_item(input);
+                    if item.is_err() {
+                        skip_to_next_case(input)?;
+                    }
+                    record(item.map(|item| rust_code.push(item)));
+                }
+            }
+        }
+
+        match errors.into_iter().reduce(|mut combined, error| {
+            combined.combine(error);
+            combined
+        }) {
+            Some(error) => Err(error),
+            None => Ok(RustyCheck {
+                globals,
+                cases,
+                benches,
+                rust_code,
+            }),
+        }
+    }
+}
+
+/// Generated tokens of a case together with its remaining tags.
+pub type TaggedCase = (Vec<Ident>, TS);
+
+/// Places every case into nested modules named after its tags.
+///
+/// A case tagged `[slow, db]` is generated as `db::slow::case_name`, so the cases
+/// of a tag can be selected with a test name filter like `cargo test db::`.
+/// Cases sharing a tag share its module.
+pub fn tag_modules(cases: Vec<TaggedCase>) -> TS {
+    let mut untagged = vec![];
+    let mut modules: BTreeMap<String, (Ident, Vec<TaggedCase>)> = BTreeMap::new();
+    for (mut tags, case) in cases {
+        if tags.is_empty() {
+            untagged.push(case);
+        } else {
+            let tag = tags.remove(0);
+            modules
+                .entry(tag.to_string())
+                .or_insert_with(|| (tag, vec![]))
+                .1
+                .push((tags, case));
+        }
+    }
+    let modules = modules.into_values().map(|(tag, cases)| {
+        let body = tag_modules(cases);
+        quote! {
+            #[cfg(test)]
+            mod #tag {
+                #[allow(unused_imports)]
+                use super::*;
+                #body
+            }
+        }
+    });
+    quote! {
+        #(#untagged)*
+        #(#modules)*
+    }
+}
+
+impl ToTokens for RustyCheck {
+    /// Converts the parsed [`RustyCheck`] into a token stream
+    /// that generates a `#[cfg(test)]` test module.
+    ///
+    /// The generated module:
+    /// - Is gated by `#[cfg(all(test, <global config>))]`
+    /// - Includes any raw Rust items from the macro input
+    /// - Expands all global constants and variables
+    /// - Expands all test `case` and `bench` blocks
+    fn to_tokens(&self, tokens: &mut TS) {
+        let (cfg_flags, consts, vars) = match &self.globals {
+            Some(Global {
+                config,
+                consts,
+                vars,
+                ..
+            }) => (config.get_cfg_flags(), consts, vars),
+            None => (TS::new(), &None, &None),
+        };
+        let config = self.get_config();
+        let cases =
+            tag_modules(
+                self.cases
+                    .clone()
+                    .into_iter()
+                    .map(|c| match c.apply_global_config(&config) {
+                        Ok(case) => (case.tags(), case.to_token_stream()),
+                        Err(error) => (vec![], error.to_compile_error()),
+                    })
+                    .chain(self.benches.iter().map(
+                        |b| match b.clone().apply_global_config(&config) {
+                            Ok(bench) => (bench.tags(), bench.to_token_stream()),
+                            Err(error) => (vec![], error.to_compile_error()),
+                        },
+                    ))
+                    .collect(),
+            );
+        let rust_code = &self.rust_code;
+        let module_name = config.get_module_name();
+        let create_module = config.get_create_module();
+        let body = quote! {
+            #(#rust_code)*
+            #consts
+            #vars
+            #cases
+        };
+        tokens.extend(match create_module {
+            false => body,
+            true => quote! {
+                #[cfg(all(test, #cfg_flags))]
+                mod #module_name {
+                    #body
+                }
+            },
+        });
+        #[cfg(feature = "criterion")]
+        tokens.extend(self.criterion(&config));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_str;
+
+    #[test]
+    fn test_parse_reports_all_broken_cases() {
+        let error = parse_str::<RustyCheck>(
+            "case a { check { 1 equl 2 } }
+            case b { check { 1 equal 2 } }
+            case c { chek { 1 equal 2 } }
+            fn helper() {}",
+        )
+        .unwrap_err();
+        let messages: Vec<String> = error.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Unknown symbol `equl`, did you mean `equal`?",
+                "Unknown case block `chek`, did you mean `check`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_suggests_case_keyword() {
+        let error = parse_str::<RustyCheck>("cas a { check { 1 equal 2 } }").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown item `cas`, did you mean `case`?"
+        );
+    }
+
+    #[test]
+    fn test_doc_examples() {
+        let rusty = parse_str::<RustyCheck>(
+            "global { cfg { module name = checks } }
+            case a { doc for crate::math::add check { add(1, 2) equal 3 } }
+            case b { check { 1 equal 1 } }",
+        )
+        .unwrap();
+        let tokens = rusty.doc_examples("calc").unwrap().to_string();
+        assert!(tokens.contains("struct __rusty_doc_checks_a { }"));
+        assert!(tokens.contains("use calc::math::*;"));
+        assert!(!tokens.contains("__rusty_doc_checks_b"));
+        assert!(!rusty.to_token_stream().to_string().contains("doctest"));
+    }
+
+    #[test]
+    fn test_parse_benches() {
+        let rusty = parse_str::<RustyCheck>(
+            "case a { check { 1 equal 1 } }
+            bench b { measure { 1 + 1 } }
+            fn helper() {}
+            bench c { cfg { tags = [slow] } measure { 1 + 1 } }",
+        )
+        .unwrap();
+        assert_eq!(rusty.benches.len(), 2);
+        let tokens = rusty.to_token_stream().to_string();
+        assert!(tokens.contains("fn b ()"));
+        assert!(tokens.contains("mod slow"));
+
+        let error = parse_str::<RustyCheck>(
+            "bench a { measure { 1 } check { 1 equl 2 } }
+            bench b { measure { 1 } }
+            bnech c { measure { 1 } }",
+        )
+        .unwrap_err();
+        let messages: Vec<String> = error.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Unknown symbol `equl`, did you mean `equal`?",
+                "Unknown item `bnech`, did you mean `bench`?",
+            ]
+        );
+    }
+
+    #[cfg(feature = "criterion")]
+    #[test]
+    fn test_criterion_module() {
+        let rusty = parse_str::<RustyCheck>(
+            "global { cfg { module name = checks } consts { N: u32 = 3 } }
+            bench b { measure { N + 1 } }",
+        )
+        .unwrap();
+        let tokens = rusty.to_token_stream().to_string();
+        let module = tokens.find("pub mod checks_criterion").unwrap();
+        assert!(tokens.find("mod checks {").unwrap() < module);
+        assert!(tokens[module..].contains("const N : u32 = 3"));
+        assert!(tokens[module..].contains("pub fn b_criterion"));
+    }
+
+    #[test]
+    fn test_tag_modules() {
+        let tokens = parse_str::<RustyCheck>(
+            "case a { cfg { tags = [slow, db] } check { 1 equal 1 } }
+            case b { cfg { tags = [db] } check { 1 equal 1 } }
+            case c { check { 1 equal 1 } }",
+        )
+        .unwrap()
+        .to_token_stream()
+        .to_string();
+        let position = |needle: &str| tokens.find(needle).unwrap();
+        assert_eq!(tokens.matches("mod db").count(), 1);
+        assert_eq!(tokens.matches("mod slow").count(), 1);
+        assert!(position("fn c") < position("mod db"));
+        assert!(position("mod db") < position("fn b"));
+        assert!(position("fn b") < position("mod slow"));
+        assert!(position("mod slow") < position("fn a"));
+    }
+
+    #[test]
+    fn test_to_spec() {
+        let spec = parse_str::<RustyCheck>(
+            "global { cfg { module name = specs, tags = [db] } consts { ROWS: usize = 3 } }
+            case a { check { 1 equal 1 } }",
+        )
+        .unwrap()
+        .to_spec()
+        .unwrap();
+        assert!(spec
+            .starts_with("# Specification of `specs`\n\nConstants:\n\n- `ROWS: usize` is `3`\n"));
+        assert!(spec.contains("## A (`a`)\n\nTags: `db`\n"));
+    }
+}
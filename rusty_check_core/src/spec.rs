@@ -0,0 +1,85 @@
+//! Rendering of cases as specification documents.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use quote::ToTokens;
+
+use super::{declaration_block::DeclarationBlock, format::render};
+
+/// Environment variable naming the directory specification documents are written to.
+pub const SPEC_OUT_VAR: &str = "RUSTY_CHECK_SPEC_OUT";
+
+/// Renders the declarations of a block as a Markdown list, such as ``- `x` is `20` ``.
+pub(crate) fn declarations_spec<K: syn::parse::Parse>(block: &DeclarationBlock<K>) -> String {
+    block
+        .declarations()
+        .into_iter()
+        .map(|(pattern, value)| {
+            format!(
+                "- `{}` is `{}`\n",
+                render(pattern.clone()),
+                render(value.to_token_stream())
+            )
+        })
+        .collect()
+}
+
+/// Writes the Markdown specification of one `rusty_check!` invocation.
+///
+/// The document is written to `<dir>/<crate name>/<source file>/<module name>.md`,
+/// where the source file is relative to the crate and without its extension,
+/// such as `spec/example/src/main/tests.md`, so every invocation of a file has its own document.
+///
+/// # Errors
+/// Returns an `io::Error` if the directory or the file cannot be written.
+pub fn write_spec(
+    dir: &Path,
+    crate_name: &str,
+    source_file: &Path,
+    module_name: &str,
+    spec: &str,
+) -> io::Result<PathBuf> {
+    let path = dir
+        .join(crate_name)
+        .join(source_file.with_extension(""))
+        .join(module_name)
+        .with_extension("md");
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, spec)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_spec() {
+        let dir = std::env::temp_dir().join(format!("rusty_check_spec_{}", std::process::id()));
+        let path = write_spec(
+            &dir,
+            "example",
+            Path::new("src/main.rs"),
+            "first",
+            "# First\n",
+        )
+        .unwrap();
+        assert_eq!(path, dir.join("example/src/main/first.md"));
+        let second = write_spec(
+            &dir,
+            "example",
+            Path::new("src/main.rs"),
+            "second",
+            "# Second\n",
+        )
+        .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# First\n");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "# Second\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
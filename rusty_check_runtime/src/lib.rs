@@ -0,0 +1,58 @@
+//! Runtime support for code generated by the `rusty_check!` macro.
+//!
+//! Crates using features of the DSL that need runtime support should add this
+//! crate next to `rusty_check`: as a dev-dependency if only their tests use
+//! these features, such as `satisfies` or result reports, and as a normal
+//! dependency if production code uses `require!` or `ensure!`, whose generated
+//! code returns a [`RustyCheckViolation`].
+
+mod report;
+mod results;
+mod violation;
+
+pub use report::{json_string, record_unstable_failure, UnstableFailure};
+pub use results::{
+    next_run_id, note_unstable_failure, read_case_results, record_case_result, to_json_report,
+    to_junit, write_reports, CaseResult, CaseStatus, JSON_REPORT_FILE_NAME, JUNIT_FILE_NAME,
+};
+pub use violation::RustyCheckViolation;
+
+/// A user-defined check, used in the DSL with the `satisfies` symbol:
+///
+/// ```ignore
+/// check {
+///     email satisfies is_valid_email()
+/// }
+/// ```
+///
+/// When the check fails, the result of [`Matcher::describe_mismatch`] is
+/// included in the failure message.
+pub trait Matcher<T: ?Sized> {
+    /// Checks if `actual` satisfies the matcher.
+    fn matches(&self, actual: &T) -> bool;
+    /// Describes why `actual` does not satisfy the matcher.
+    fn describe_mismatch(&self, actual: &T) -> String;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct IsEven;
+
+    impl Matcher<i32> for IsEven {
+        fn matches(&self, actual: &i32) -> bool {
+            actual % 2 == 0
+        }
+        fn describe_mismatch(&self, actual: &i32) -> String {
+            format!("{} is odd", actual)
+        }
+    }
+
+    #[test]
+    fn test_matcher() {
+        assert!(IsEven.matches(&2));
+        assert!(!IsEven.matches(&3));
+        assert_eq!(IsEven.describe_mismatch(&3), "3 is odd");
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+/// Serializes appends of test threads running in parallel, to any records file.
+static RECORDS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Represents a failed check of an `unstable` case, written as one JSON line.
+///
+/// A `UnstableFailure` consists of:
+/// - `case`: The name of the case.
+/// - `module`: The module path of the generated test.
+/// - `comment`: The failure message of the check.
+/// - `timestamp`: Seconds since the Unix epoch when the check failed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnstableFailure<'a> {
+    pub case: &'a str,
+    pub module: &'a str,
+    pub comment: &'a str,
+    pub timestamp: u64,
+}
+
+impl UnstableFailure<'_> {
+    /// Formats the failure as a JSON object on a single line.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a failure is always serializable")
+    }
+}
+
+/// Quotes and escapes `text` as a JSON string.
+pub fn json_string(text: &str) -> String {
+    serde_json::to_string(text).expect("a string is always serializable")
+}
+
+/// Returns the seconds since the Unix epoch, the timestamp of records.
+pub(crate) fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Appends `record` as one JSON line to the file at `path`,
+/// creating the file and its directories if needed.
+///
+/// # Errors
+/// Returns an `io::Error` if the file cannot be created or written.
+pub(crate) fn append_record(path: &Path, record: &impl Serialize) -> io::Result<()> {
+    let line = serde_json::to_string(record)? + "\n";
+    let _guard = RECORDS_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Appends a record of a failed `unstable` check to the JSON-lines file at `path`,
+/// creating the file and its directories if needed.
+///
+/// # Errors
+/// Returns an `io::Error` if the file cannot be created or written.
+pub fn record_unstable_failure(
+    path: impl AsRef<Path>,
+    case: &str,
+    module: &str,
+    comment: &str,
+) -> io::Result<()> {
+    let record = UnstableFailure {
+        case,
+        module,
+        comment,
+        timestamp: timestamp(),
+    };
+    append_record(path.as_ref(), &record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let record = UnstableFailure {
+            case: "flaky",
+            module: "crate::tests",
+            comment: "a equal \"b\"\n",
+            timestamp: 7,
+        };
+        assert_eq!(
+            record.to_json(),
+            r#"{"case":"flaky","module":"crate::tests","comment":"a equal \"b\"\n","timestamp":7}"#
+        );
+    }
+
+    #[test]
+    fn test_record_unstable_failure() {
+        let dir = std::env::temp_dir().join(format!("rusty_check_report_{}", std::process::id()));
+        let path = dir.join("nested").join("unstable.jsonl");
+        record_unstable_failure(&path, "a", "m", "first").unwrap();
+        record_unstable_failure(&path, "b", "m", "second").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(r#"{"case":"a","module":"m","comment":"first","timestamp":"#));
+        assert!(lines[1].contains(r#""case":"b""#));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,419 @@
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::{append_record, timestamp};
+
+/// Name of the JUnit XML report written next to the records by [`write_reports`].
+pub const JUNIT_FILE_NAME: &str = "junit.xml";
+/// Name of the JSON report written next to the records by [`write_reports`].
+pub const JSON_REPORT_FILE_NAME: &str = "results.json";
+
+/// Identifier of the next run of a case.
+static NEXT_RUN: AtomicU64 = AtomicU64::new(0);
+/// Failed unstable checks noted by running cases, as `(run, comment)`.
+static UNSTABLE_FAILURES: Mutex<Vec<(u64, String)>> = Mutex::new(vec![]);
+
+/// Represents the outcome of a case.
+///
+/// Variants:
+/// - `Passed`: All checks passed.
+/// - `Failed`: The case panicked, usually because a check failed.
+/// - `Unstable`: A check of an `unstable` case failed without failing the test.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseStatus {
+    Passed,
+    Failed,
+    Unstable,
+}
+
+impl CaseStatus {
+    /// Returns the name of the status used in records and reports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CaseStatus::Passed => "passed",
+            CaseStatus::Failed => "failed",
+            CaseStatus::Unstable => "unstable",
+        }
+    }
+}
+
+/// Represents the result of one run of a case, written as one JSON line.
+///
+/// A `CaseResult` consists of:
+/// - `case`: The name of the case.
+/// - `module`: The module path of the generated test.
+/// - `status`: Whether the case passed, failed or failed an unstable check.
+/// - `duration`: The time the case ran, including retries.
+/// - `comment`: The failure message with the values of the check, if it did not pass.
+/// - `timestamp`: Seconds since the Unix epoch when the case finished.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaseResult {
+    pub case: String,
+    pub module: String,
+    pub status: CaseStatus,
+    #[serde(with = "duration_seconds")]
+    pub duration: Duration,
+    pub comment: Option<String>,
+    pub timestamp: u64,
+}
+
+impl CaseResult {
+    /// Formats the result as a JSON object on a single line.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a result is always serializable")
+    }
+
+    /// Parses a result from a line written by [`CaseResult::to_json`].
+    ///
+    /// Returns `None` if the line is not such a record.
+    pub fn from_json(line: &str) -> Option<CaseResult> {
+        serde_json::from_str(line).ok()
+    }
+}
+
+/// Writes durations of records as seconds, such as `1.5`.
+mod duration_seconds {
+    use std::time::Duration;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Duration::try_from_secs_f64(f64::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Returns a new identifier for a run of a case, passed to [`note_unstable_failure`]
+/// and [`record_case_result`].
+///
+/// Runs of the same case in parallel, or on other threads like those of timeouts,
+/// are told apart by it.
+pub fn next_run_id() -> u64 {
+    NEXT_RUN.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Notes a failed check of an `unstable` case during `run`, so its result is recorded
+/// as unstable. Only the latest failure of a run is kept.
+pub fn note_unstable_failure(run: u64, comment: &str) {
+    let mut failures = UNSTABLE_FAILURES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    failures.retain(|(noted, _)| *noted != run);
+    failures.push((run, comment.to_owned()));
+}
+
+/// Returns the comment of a failed unstable check noted during `run`, removing it.
+fn take_unstable_failure(run: u64) -> Option<String> {
+    let mut failures = UNSTABLE_FAILURES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let index = failures.iter().position(|(noted, _)| *noted == run)?;
+    Some(failures.remove(index).1)
+}
+
+/// Returns the message of a panic, such as the failure message of a check.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Case panicked".to_owned()
+    }
+}
+
+/// Appends the result of `run` of a case to the JSON-lines file at `path`,
+/// creating the file and its directories if needed.
+///
+/// The case failed if `panic` holds the payload of its panic. Otherwise it is
+/// unstable if [`note_unstable_failure`] was called for the run, and passed if not.
+///
+/// The file is never truncated, as tests of one run may record results from several
+/// processes; `cargo rusty-check test` removes it before running the cases. Records
+/// of earlier plain `cargo test` runs are kept and read back by [`read_case_results`].
+///
+/// # Errors
+/// Returns an `io::Error` if the file cannot be created or written.
+pub fn record_case_result(
+    path: impl AsRef<Path>,
+    run: u64,
+    case: &str,
+    module: &str,
+    duration: Duration,
+    panic: Option<&(dyn Any + Send)>,
+) -> io::Result<()> {
+    let path = path.as_ref();
+    let unstable = take_unstable_failure(run);
+    let (status, comment) = match (panic, unstable) {
+        (Some(payload), _) => (CaseStatus::Failed, Some(panic_message(payload))),
+        (None, Some(comment)) => (CaseStatus::Unstable, Some(comment)),
+        (None, None) => (CaseStatus::Passed, None),
+    };
+    let record = CaseResult {
+        case: case.to_owned(),
+        module: module.to_owned(),
+        status,
+        duration,
+        comment,
+        timestamp: timestamp(),
+    };
+    append_record(path, &record)
+}
+
+/// Reads the results recorded in the JSON-lines file at `path`.
+///
+/// When a case was recorded several times, only its latest result is kept.
+/// Results are sorted by module and case.
+///
+/// # Errors
+/// Returns an `io::Error` if the file cannot be read or a line is not a result.
+pub fn read_case_results(path: impl AsRef<Path>) -> io::Result<Vec<CaseResult>> {
+    let path = path.as_ref();
+    let mut results = BTreeMap::new();
+    for (index, line) in fs::read_to_string(path)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let result = CaseResult::from_json(line).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: invalid case result", path.display(), index + 1),
+            )
+        })?;
+        results.insert((result.module.clone(), result.case.clone()), result);
+    }
+    Ok(results.into_values().collect())
+}
+
+/// Escapes `text` for XML attributes and text, dropping characters XML does not allow.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' | '\r' | '\t' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Counts the results with `status`.
+fn count(results: &[&CaseResult], status: CaseStatus) -> usize {
+    results
+        .iter()
+        .filter(|result| result.status == status)
+        .count()
+}
+
+/// Sums the durations of the results in seconds.
+fn seconds(results: &[&CaseResult]) -> f64 {
+    results
+        .iter()
+        .map(|result| result.duration.as_secs_f64())
+        .sum()
+}
+
+/// Formats the results as a JUnit XML report with a test suite per module.
+///
+/// Failed cases get a `failure` element with the failure message, unstable cases
+/// pass and carry the message of the failed check in `system-err`.
+pub fn to_junit(results: &[CaseResult]) -> String {
+    let all: Vec<&CaseResult> = results.iter().collect();
+    let mut suites: BTreeMap<&str, Vec<&CaseResult>> = BTreeMap::new();
+    for result in results {
+        suites.entry(&result.module).or_default().push(result);
+    }
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml += &format!(
+        "<testsuites name=\"rusty_check\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+        all.len(),
+        count(&all, CaseStatus::Failed),
+        seconds(&all)
+    );
+    for (module, cases) in suites {
+        xml += &format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\" time=\"{:.3}\">\n",
+            xml_escape(module),
+            cases.len(),
+            count(&cases, CaseStatus::Failed),
+            seconds(&cases)
+        );
+        for case in cases {
+            let open = format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                xml_escape(&case.case),
+                xml_escape(&case.module),
+                case.duration.as_secs_f64()
+            );
+            let comment = xml_escape(case.comment.as_deref().unwrap_or_default());
+            xml += &match case.status {
+                CaseStatus::Passed => format!("{}/>\n", open),
+                CaseStatus::Failed => format!(
+                    "{}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                    open, comment, comment
+                ),
+                CaseStatus::Unstable => format!(
+                    "{}>\n      <system-err>Unstable test failed, {}</system-err>\n    </testcase>\n",
+                    open, comment
+                ),
+            };
+        }
+        xml += "  </testsuite>\n";
+    }
+    xml += "</testsuites>\n";
+    xml
+}
+
+/// Represents the JSON report written by [`write_reports`].
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    tests: usize,
+    passed: usize,
+    failed: usize,
+    unstable: usize,
+    duration: f64,
+    cases: &'a [CaseResult],
+}
+
+/// Formats the results as a JSON report with the counts of every status and the results.
+pub fn to_json_report(results: &[CaseResult]) -> String {
+    let all: Vec<&CaseResult> = results.iter().collect();
+    let report = JsonReport {
+        tests: all.len(),
+        passed: count(&all, CaseStatus::Passed),
+        failed: count(&all, CaseStatus::Failed),
+        unstable: count(&all, CaseStatus::Unstable),
+        duration: seconds(&all),
+        cases: results,
+    };
+    serde_json::to_string_pretty(&report).expect("a report is always serializable") + "\n"
+}
+
+/// Collects the results recorded in the JSON-lines file at `records` and writes
+/// a JUnit XML report and a JSON report into the same directory.
+///
+/// Returns the paths of the written reports and the results.
+///
+/// # Errors
+/// Returns an `io::Error` if the records cannot be read or the reports cannot be written.
+pub fn write_reports(records: impl AsRef<Path>) -> io::Result<(PathBuf, PathBuf, Vec<CaseResult>)> {
+    let records = records.as_ref();
+    let results = read_case_results(records)?;
+    let dir = records.parent().unwrap_or(Path::new(""));
+    let junit = dir.join(JUNIT_FILE_NAME);
+    let json = dir.join(JSON_REPORT_FILE_NAME);
+    fs::write(&junit, to_junit(&results))?;
+    fs::write(&json, to_json_report(&results))?;
+    Ok((junit, json, results))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(case: &str, status: CaseStatus, comment: Option<&str>) -> CaseResult {
+        CaseResult {
+            case: case.to_owned(),
+            module: "crate::tests".to_owned(),
+            status,
+            duration: Duration::from_millis(1500),
+            comment: comment.map(str::to_owned),
+            timestamp: 7,
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let record = result("a", CaseStatus::Failed, Some("a equal \"b\"\n\u{1}"));
+        let json = record.to_json();
+        assert_eq!(
+            json,
+            r#"{"case":"a","module":"crate::tests","status":"failed","duration":1.5,"comment":"a equal \"b\"\n\u0001","timestamp":7}"#
+        );
+        assert_eq!(CaseResult::from_json(&json), Some(record));
+        let passed = result("b", CaseStatus::Passed, None);
+        assert_eq!(CaseResult::from_json(&passed.to_json()), Some(passed));
+        assert_eq!(CaseResult::from_json(r#"{"case":"a"}"#), None);
+        assert_eq!(CaseResult::from_json("{} trailing"), None);
+    }
+
+    #[test]
+    fn test_to_junit() {
+        let xml = to_junit(&[
+            result("passes", CaseStatus::Passed, None),
+            result("fails", CaseStatus::Failed, Some("a < b where, a=2")),
+            result("flaky", CaseStatus::Unstable, Some("1 equal 2")),
+        ]);
+        assert!(xml.contains(
+            "<testsuites name=\"rusty_check\" tests=\"3\" failures=\"1\" time=\"4.500\">"
+        ));
+        assert!(xml.contains("<testsuite name=\"crate::tests\" tests=\"3\" failures=\"1\""));
+        assert!(
+            xml.contains("<testcase name=\"passes\" classname=\"crate::tests\" time=\"1.500\"/>")
+        );
+        assert!(
+            xml.contains("<failure message=\"a &lt; b where, a=2\">a &lt; b where, a=2</failure>")
+        );
+        assert!(xml.contains("<system-err>Unstable test failed, 1 equal 2</system-err>"));
+    }
+
+    #[test]
+    fn test_write_reports() {
+        let dir = std::env::temp_dir().join(format!("rusty_check_results_{}", std::process::id()));
+        let records = dir.join("results.jsonl");
+        let module = "results::tests";
+        let (first, second, flaky) = (next_run_id(), next_run_id(), next_run_id());
+        note_unstable_failure(flaky, "1 equal 2");
+        let panic: Box<dyn Any + Send> = Box::new("check failed");
+        let record = |run, case, panic| {
+            record_case_result(&records, run, case, module, Duration::ZERO, panic).unwrap()
+        };
+        record(first, "fails", None);
+        record(second, "fails", Some(&*panic));
+        record(flaky, "flaky", None);
+        let (junit, json, results) = write_reports(&records).unwrap();
+        let statuses: Vec<(&str, CaseStatus, Option<&str>)> = results
+            .iter()
+            .map(|result| {
+                (
+                    result.case.as_str(),
+                    result.status,
+                    result.comment.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("fails", CaseStatus::Failed, Some("check failed")),
+                ("flaky", CaseStatus::Unstable, Some("1 equal 2")),
+            ]
+        );
+        assert_eq!(junit, dir.join(JUNIT_FILE_NAME));
+        assert!(fs::read_to_string(&json).unwrap().starts_with(
+            "{\n  \"tests\": 2,\n  \"passed\": 0,\n  \"failed\": 1,\n  \"unstable\": 1,"
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
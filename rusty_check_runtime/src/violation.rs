@@ -0,0 +1,61 @@
+use std::{error::Error, fmt};
+
+/// Represents a failed `require!` or `ensure!` contract check.
+///
+/// A `RustyCheckViolation` consists of:
+/// - `message`: The kind of the contract and its conditions, such as
+///   `Precondition failed: a less than b`.
+/// - `values`: The names and debug representations of the values in the conditions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RustyCheckViolation {
+    pub message: String,
+    pub values: Vec<(String, String)>,
+}
+
+impl RustyCheckViolation {
+    pub fn new(message: impl Into<String>, values: Vec<(String, String)>) -> Self {
+        RustyCheckViolation {
+            message: message.into(),
+            values,
+        }
+    }
+}
+
+impl fmt::Display for RustyCheckViolation {
+    /// Formats a `RustyCheckViolation` as a human-readable string,
+    /// such as `Precondition failed: a less than b where, a=2, b=1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        for (i, (name, value)) in self.values.iter().enumerate() {
+            let separator = if i == 0 { " where, " } else { ", " };
+            write!(f, "{}{}={}", separator, name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for RustyCheckViolation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let violation = RustyCheckViolation::new(
+            "Precondition failed: a less than b",
+            vec![
+                ("a".to_owned(), "2".to_owned()),
+                ("b".to_owned(), "1".to_owned()),
+            ],
+        );
+        assert_eq!(
+            violation.to_string(),
+            "Precondition failed: a less than b where, a=2, b=1"
+        );
+        assert_eq!(
+            RustyCheckViolation::new("Postcondition failed: ok", vec![]).to_string(),
+            "Postcondition failed: ok"
+        );
+    }
+}
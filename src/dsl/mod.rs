@@ -1 +0,0 @@
-pub mod proc_macros;
@@ -1,2 +0,0 @@
-pub mod helpers;
-pub mod rusty_check;
@@ -1,38 +0,0 @@
-syn::custom_keyword!(case);
-
-syn::custom_keyword!(given);
-// keyword do
-syn::custom_keyword!(check);
-// condition keywords
-syn::custom_keyword!(not);
-syn::custom_keyword!(equal);
-syn::custom_keyword!(less);
-syn::custom_keyword!(greater);
-syn::custom_keyword!(than);
-syn::custom_keyword!(or);
-syn::custom_keyword!(and);
-// looping trough iterators in condition
-// keyword for
-syn::custom_keyword!(each);
-syn::custom_keyword!(any);
-
-// expression keywords
-syn::custom_keyword!(rust);
-
-syn::custom_keyword!(global);
-//cfg
-syn::custom_keyword!(cfg);
-
-syn::custom_keyword!(comment);
-syn::custom_keyword!(simple);
-syn::custom_keyword!(show);
-syn::custom_keyword!(values);
-
-syn::custom_keyword!(unstable);
-
-syn::custom_keyword!(module);
-syn::custom_keyword!(name);
-syn::custom_keyword!(create);
-//cfg
-syn::custom_keyword!(consts);
-syn::custom_keyword!(vars);
@@ -1,10 +0,0 @@
-pub mod case;
-pub mod check;
-pub mod compute;
-pub mod condition;
-pub mod conditions;
-pub mod configure;
-pub mod declaration_block;
-pub mod global;
-pub mod keywords;
-pub mod rusty_check;
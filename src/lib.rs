@@ -1,87 +1,123 @@
-mod dsl;
-use dsl::proc_macros::rusty_check::{
-    configure::{CommentType, Config, ConfigOption, ConfigOptionName},
-    rusty_check::RustyCheck,
-};
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
 use quote::ToTokens;
-use std::{collections::HashMap, env, error::Error, fs};
-use syn::{parse_macro_input, parse_str};
-use toml::{Table, Value};
-/// RustyCheck procedural macro that processes the `rusty_check!` DSL.
-/// Follows grammar from this diagram:
+use rusty_check_core::{
+    config_file::{discover_config, read_config_file, ConfigFileError},
+    spec::{write_spec, SPEC_OUT_VAR},
+    Check, Conditions, Config, Contract, ContractKind, RustyCase, RustyCheck,
+};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+use syn::parse_macro_input;
 
-fn read_config() -> Result<Config, Box<dyn Error>> {
-    if let Some(path) = option_env!("RUSTY_CONFIG") {
-        let mut p = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        p.push(path);
-        let path = p.clone();
-        let contents = match fs::read_to_string(&p) {
-            Ok(c) => c,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                return Err(Box::new(e));
-            }
-            Err(e) => return Err(Box::new(e)),
-        };
-        let config: HashMap<String, Value> = toml::from_str(&contents)?;
-        let mut options: Vec<(ConfigOptionName, ConfigOption)> = Vec::new();
-        for (k, v) in config {
-            options.push(match k.as_str() {
-                "moduleName" => (
-                    ConfigOptionName::ModuleName,
-                    ConfigOption::ModuleName {
-                        name: Ident::new(v.as_str().unwrap(), proc_macro2::Span::call_site()),
-                    },
-                ),
-                "createModule" => (
-                    ConfigOptionName::CreateModule,
-                    ConfigOption::CreateModule {
-                        value: v.as_bool().unwrap(),
-                    },
-                ),
-                "unstable" => (
-                    ConfigOptionName::TestUnstable,
-                    ConfigOption::TestUnstable {
-                        value: v.as_bool().unwrap(),
-                    },
-                ),
-                "cfg" => (
-                    ConfigOptionName::CfgFlags,
-                    ConfigOption::CfgFlags {
-                        flags: parse_str(v.as_str().unwrap())?,
-                    },
-                ),
-                "comment" => (
-                    ConfigOptionName::CommentType,
-                    ConfigOption::CommentType {
-                        comment_type: match v.as_str().unwrap() {
-                            "simple" => CommentType::Simple,
-                            "showValues" => CommentType::ShowValues,
-                            _ => todo!(),
-                        },
-                    },
-                ),
-                _ => todo!(),
-            });
-        }
-        Ok(Config {
-            options: options.into_iter().collect(),
-        })
-    } else {
-        Ok(Config::default())
+/// Environment variable naming a config file, relative to the crate.
+const CONFIG_VAR: &str = "RUSTY_CONFIG";
+
+/// Reads the config of the crate being compiled.
+///
+/// Configs are discovered from the crate directory up to the workspace root,
+/// a file named by the `RUSTY_CONFIG` environment variable, relative to the crate,
+/// takes precedence over them.
+///
+/// Also returns items to place in the generated code, which make cargo expand
+/// the macro again when one of the config files or the variable changes.
+fn read_config() -> Result<(Config, proc_macro2::TokenStream), ConfigFileError> {
+    let Ok(crate_dir) = env::var("CARGO_MANIFEST_DIR") else {
+        return Ok((Config::default(), proc_macro2::TokenStream::new()));
+    };
+    let crate_dir = PathBuf::from(crate_dir);
+    let (mut config, mut files) = discover_config(&crate_dir)?;
+    if let Ok(path) = env::var(CONFIG_VAR) {
+        let path = crate_dir.join(path);
+        config = read_config_file(&path)?.merge_with_other(&config);
+        files.push(path);
     }
+    let files = files.iter().map(|file| file.display().to_string());
+    let tracking = quote::quote! {
+        const _: ::core::option::Option<&str> = ::core::option_env!(#CONFIG_VAR);
+        #(const _: &[u8] = ::core::include_bytes!(#files);)*
+    };
+    Ok((config, tracking))
+}
+
+/// Writes the specification document of `rusty` if `RUSTY_CHECK_SPEC_OUT` names a directory.
+///
+/// The document is named after the crate, the source file and the module name of the invocation.
+fn write_spec_document(rusty: &RustyCheck) -> syn::Result<()> {
+    let Ok(dir) = env::var(SPEC_OUT_VAR) else {
+        return Ok(());
+    };
+    let crate_name = env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "crate".to_owned());
+    let source_file = proc_macro::Span::call_site().file();
+    let source_file = env::var("CARGO_MANIFEST_DIR")
+        .ok()
+        .and_then(|crate_dir| {
+            Path::new(&source_file)
+                .strip_prefix(crate_dir)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_else(|| PathBuf::from(&source_file));
+    write_spec(
+        Path::new(&dir),
+        &crate_name,
+        &source_file,
+        &rusty.get_config().get_module_name().to_string(),
+        &rusty.to_spec()?,
+    )
+    .map(|_| ())
+    .map_err(|e| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!("Failed to write specification to {}: {}", dir, e),
+        )
+    })
 }
 
+/// RustyCheck procedural macro that processes the `rusty_check!` DSL.
+///
+/// The generated tests read environment variables when they run:
+///
+/// - `RUSTY_CHECK_UNSTABLE=fail` makes failed unstable checks fail the test.
+/// - `RUSTY_CHECK_COMMENT=simple` trims failure messages to the conditions.
+/// - `RUSTY_CHECK_ONLY_TAGS=db,slow` skips every case that has none of the listed tags,
+///   including all cases without tags. Skipped cases are reported as passed by the
+///   test harness and print a `Skipped case` line. To leave them out of the run
+///   instead, select cases by the tag modules of their test names, as in
+///   `cargo test db::`, or with `cargo rusty-check test --tag db`.
+///
+/// A case with a `doc for crate::path::to::function` clause also becomes a doctest.
+/// The example is attached to a hidden item next to the test module, not to the
+/// documented function, so it is run by `cargo test --doc` but not shown in its
+/// documentation. Only invocations in the library of a crate are collected by
+/// `rustdoc`, the examples of invocations in `tests/*.rs` never run.
+///
+/// Follows grammar from this diagram:
 #[proc_macro]
 pub fn rusty_check(input: TokenStream) -> TokenStream {
     // let config = read_config().unwrap();
     // let rusty = parse_macro_input!(input as RustyCheck).apply_config_file(&config);
     // rusty.to_token_stream().into()
     match read_config() {
-        Ok(config) => {
+        Ok((config, tracking)) => {
             let rusty = parse_macro_input!(input as RustyCheck).apply_config_file(&config);
-            rusty.to_token_stream().into()
+            if let Err(error) = write_spec_document(&rusty) {
+                return error.to_compile_error().into();
+            }
+            let mut tokens = rusty.to_token_stream();
+            let crate_name = env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "crate".to_owned());
+            match rusty.doc_examples(&crate_name) {
+                Ok(examples) => tokens.extend(examples),
+                Err(error) => return error.to_compile_error().into(),
+            }
+            // Reading the variable in the generated code makes cargo expand the
+            // macro again when it changes, so the documents are written.
+            tokens.extend(quote::quote! {
+                const _: ::core::option::Option<&str> = ::core::option_env!(#SPEC_OUT_VAR);
+                #tracking
+            });
+            tokens.into()
         }
         Err(e) => {
             // Emit a proper compiler error instead of panicking
@@ -92,3 +128,106 @@ pub fn rusty_check(input: TokenStream) -> TokenStream {
         }
     }
 }
+
+/// Attribute form of a `rusty_check!` case, written as a plain function:
+///
+/// ```
+/// use rusty_check::rusty_case;
+///
+/// /// Adds two numbers.
+/// #[rusty_case(comment = simple)]
+/// fn addition() {
+///     given! { a = 2, b = 3 }
+///     let sum = a + b;
+///     check! { sum equal 5 }
+/// }
+/// ```
+///
+/// The arguments are the options of a case `cfg` block, `given!` and `check!`
+/// are the `given` and `check` blocks and the statements in between the `do` block.
+/// The doc comments and visibility of the function are kept on the generated test.
+#[proc_macro_attribute]
+pub fn rusty_case(args: TokenStream, item: TokenStream) -> TokenStream {
+    let (config, tracking) = match read_config() {
+        Ok(config) => config,
+        Err(e) => {
+            let msg = format!("Failed to read config: {}", e);
+            return syn::Error::new(proc_macro2::Span::call_site(), msg)
+                .to_compile_error()
+                .into();
+        }
+    };
+    match RustyCase::parse(args.into(), item.into())
+        .and_then(|case| case.apply_config_file(&config))
+    {
+        Ok(case) => {
+            let case = case.to_token_stream();
+            quote::quote! { #case #tracking }.into()
+        }
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Asserts conditions written in the `check` block language, in any code:
+///
+/// ```ignore
+/// check!(a less than b and for each x in &v, x greater than 0);
+/// ```
+///
+/// Expands to `assert!` with a message showing the conditions and the values in them.
+#[proc_macro]
+pub fn check(input: TokenStream) -> TokenStream {
+    let conditions = parse_macro_input!(input as Conditions);
+    let assertion = proc_macro2::Ident::new("assert", proc_macro2::Span::call_site());
+    Check::new(conditions).to_assertion(assertion).into()
+}
+
+/// Like [`check!`], but expands to `debug_assert!`,
+/// so the conditions are only checked in builds with debug assertions.
+#[proc_macro]
+pub fn debug_check(input: TokenStream) -> TokenStream {
+    let conditions = parse_macro_input!(input as Conditions);
+    let assertion = proc_macro2::Ident::new("debug_assert", proc_macro2::Span::call_site());
+    Check::new(conditions).to_assertion(assertion).into()
+}
+
+/// Expands a `require!` or `ensure!` contract with the config read from config files.
+fn contract(input: TokenStream, kind: ContractKind) -> TokenStream {
+    let (config, tracking) = match read_config() {
+        Ok(config) => config,
+        Err(e) => {
+            let msg = format!("Failed to read config: {}", e);
+            return syn::Error::new(proc_macro2::Span::call_site(), msg)
+                .to_compile_error()
+                .into();
+        }
+    };
+    let parser = |input: syn::parse::ParseStream| Contract::parse(input, kind);
+    let contract = parse_macro_input!(input with parser);
+    let contract = contract.apply_config_file(&config);
+    quote::quote! {{ #tracking #contract }}.into()
+}
+
+/// Checks preconditions written in the `check` block language in production code:
+///
+/// ```ignore
+/// fn withdraw(balance: u32, amount: u32) -> Result<u32, RustyCheckViolation> {
+///     require!(amount less than balance or amount equal balance);
+///     Ok(balance - amount)
+/// }
+/// ```
+///
+/// If the conditions do not hold, the enclosing function returns
+/// `Err(RustyCheckViolation { message, values })`, converted with `From`.
+/// With `violation = panic`, set in config files or in a leading
+/// `cfg { violation = panic },`, it panics instead.
+#[proc_macro]
+pub fn require(input: TokenStream) -> TokenStream {
+    contract(input, ContractKind::Require)
+}
+
+/// Checks postconditions, like [`require!`] checks preconditions.
+#[proc_macro]
+pub fn ensure(input: TokenStream) -> TokenStream {
+    contract(input, ContractKind::Ensure)
+}
@@ -0,0 +1,54 @@
+use rusty_check::rusty_check;
+rusty_check! {
+    global {
+        cfg {
+            warmup = 5,
+            iterations = 50,
+        }
+    }
+
+    bench sum_of_range {
+        given {
+            numbers = (0..100u64).collect::<Vec<_>>(),
+        }
+        measure {
+            numbers.iter().sum::<u64>()
+        }
+        check {
+            elapsed less than 50ms
+            and p99 equal or greater than median
+            and min equal or less than mean
+        }
+    }
+
+    bench without_budget {
+        cfg {
+            iterations = 1,
+        }
+        measure {
+            std::thread::yield_now()
+        }
+    }
+}
+
+rusty_check! {
+    global {
+        cfg {
+            module name = recorded,
+            result report = "target/rusty_check/test_bench/results.jsonl",
+            unstable = true,
+        }
+    }
+
+    bench unstable_budget {
+        cfg {
+            iterations = 1,
+        }
+        measure {
+            std::thread::sleep(std::time::Duration::from_millis(1))
+        }
+        check {
+            elapsed less than 1ns
+        }
+    }
+}
@@ -1,16 +1,17 @@
 use rusty_check::rusty_check;
 #[cfg(true)]
 rusty_check! {
-    case complex_conf{
+    case complex_conf {
         cfg {
             unstable = true,
-            cfg = true
+            cfg = true,
         }
         check {
             1 equal 2
         }
     }
-    case complex_conf_rev{
+
+    case complex_conf_rev {
         cfg {
             cfg = true,
             unstable = true,
@@ -19,7 +20,8 @@ rusty_check! {
             1 equal 2
         }
     }
-    case always_off{
+
+    case always_off {
         cfg {
             cfg = false,
             unstable = true,
@@ -28,9 +30,10 @@ rusty_check! {
             1 equal 2
         }
     }
-    case always_off_complex{
+
+    case always_off_complex {
         cfg {
-            cfg = all(true,true,false),
+            cfg = all(true, true, false),
             unstable = true,
         }
         check {
@@ -38,9 +41,33 @@ rusty_check! {
         }
     }
 
-    case comment_option{
+    case lint_deny {
+        cfg {
+            lint = deny,
+        }
+        given {
+            x = 2,
+        }
+        do {
+            let y = x * 2;
+        }
+        check {
+            y equal 4
+        }
+    }
+
+    case profile_option {
+        cfg {
+            profile = flaky,
+        }
+        check {
+            1 equal 2
+        }
+    }
+
+    case comment_option {
         cfg {
-            comment=simple,
+            comment = simple,
             unstable = true,
         }
         check {
@@ -0,0 +1,23 @@
+use rusty_check::{check, debug_check};
+
+/// Returns the positive values, checking the result in debug builds.
+fn positives(values: &[i32]) -> Vec<i32> {
+    let result: Vec<i32> = values.iter().copied().filter(|x| *x > 0).collect();
+    debug_check!(for each x in &result, *x greater than 0);
+    result
+}
+
+#[test]
+fn check_in_ordinary_test() {
+    let (a, b) = (1, 2);
+    let v = positives(&[3, -1, 4]);
+    check!(a less than b and for each x in &v, *x greater than 0);
+    check!(v equal vec![3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "a greater than b where, a=1, b=2")]
+fn check_failure_shows_values() {
+    let (a, b) = (1, 2);
+    check!(a greater than b);
+}
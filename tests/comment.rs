@@ -1,3 +1,4 @@
+#![allow(unexpected_cfgs)]
 use rusty_check::rusty_check;
 
 rusty_check! {
@@ -8,21 +9,22 @@ rusty_check! {
             cfg = feature = "pls",
         }
     }
+
     case test_cond_comment {
         given {
             a = 2,
-            b = 3
+            b = 3,
         }
         check {
             a less than b
         }
     }
+
     case test_comp_comment {
         given {
             a = 2,
             b = 3,
-            c = 4
-
+            c = 4,
         }
         check {
             a equal b or b less than c
@@ -31,18 +33,18 @@ rusty_check! {
 
     case test_loop_comment {
         given {
-            a = vec![1,2,3],
-            b = 4
-
+            a = vec![1, 2, 3],
+            b = 4,
         }
         check {
             for each v in &a, *v less than b
         }
     }
+
     case test_negation {
         given {
             a = 2,
-            b = 3
+            b = 3,
         }
         check {
             a not greater than b
@@ -51,6 +53,7 @@ rusty_check! {
 
     mod data_tests{
     #[derive(Debug,PartialEq)]
+    #[allow(dead_code)]
     pub enum MyEnum {
         Value,
         No,
@@ -66,21 +69,24 @@ rusty_check! {
             }
         }
     }
+
     case test_enum {
         given {
-            v = data_tests::MyEnum::Value
+            v = data_tests::MyEnum::Value,
         }
         check {
             v equal data_tests::MyEnum::Value
         }
     }
+
     fn test_nothing(x:i32) -> i32{
         println!("I runn");
         x
     }
+
     case test_with_fun {
         given {
-            v = 1
+            v = 1,
         }
         check {
             v equal test_nothing(v)
@@ -89,19 +95,19 @@ rusty_check! {
 
     case test_method {
         given {
-            v = data_tests::MyStruct { val: 10}
+            v = data_tests::MyStruct { val: 10 },
         }
         check {
-            v.add_five() equal v.val+5
+            v.add_five() equal v.val + 5
         }
     }
+
     case test_unstable {
         cfg {
-            unstable = true
+            unstable = true,
         }
         check {
             1 equal 2
         }
     }
-
 }
@@ -1,8 +1,8 @@
-use rusty_check::rusty_check;
+use rusty_check::{check, rusty_check};
 rusty_check! {
     case loop_cond_on_vec {
         given {
-            v = vec![1,2]
+            v = vec![1, 2],
         }
         check {
             for each n in v.clone(), n greater than 0
@@ -11,28 +11,31 @@ rusty_check! {
 
     case loop_cond_on_vec_with_borrowing {
         given {
-            v = vec![1,2]
+            v = vec![1, 2],
         }
         check {
             for each n in &v, *n greater than 0
         }
     }
+
     case loop_cond_on_arr {
         given {
-            arr = [1;5]
+            arr = [1; 5],
         }
         check {
             for each n in arr, n greater than 0
         }
     }
+
     case loop_cond_on_arr_with_borrowing {
         given {
-            arr = [1;5]
+            arr = [1; 5],
         }
         check {
             for each n in &arr, *n greater than 0
         }
     }
+
     case loop_cond_on_slice {
         given {
             v = vec![10, 20, 30, 40, 50],
@@ -40,37 +43,248 @@ rusty_check! {
             part_slice: &[i32] = &v[1..4],
         }
         check {
-            for each n in part_slice, *n greater than 0 and for any n in full_slice, *n less than 100
+            for each n in part_slice, *n greater than 0
+            and for any n in full_slice, *n less than 100
         }
     }
 
     mod enum_tests{
     #[derive(Debug,PartialEq)]
+    #[allow(dead_code)]
     pub enum MyEnum {
         Value,
         No,
     }}
+
     case test_enum {
         given {
-            v = enum_tests::MyEnum::Value
+            v = enum_tests::MyEnum::Value,
         }
         check {
             v equal enum_tests::MyEnum::Value
         }
     }
+
     fn test_nothing(x:i32) -> i32{
         println!("I runn");
         x
     }
+
     case test_with_fun {
         given {
-            v = 1
+            v = 1,
         }
         check {
             v equal test_nothing(v)
         }
     }
 
+    case loop_cond_with_tuple_pattern {
+        given {
+            pairs = vec![(1, 2), (3, 4)],
+        }
+        check {
+            for each (a, b) in &pairs, *a less than *b
+        }
+    }
+
+    case loop_cond_on_map_entries {
+        given {
+            map = std::collections::HashMap::from([("a", 1), ("b", 2)]),
+        }
+        check {
+            for each (k, v) in &map, k.len() equal 1 and *v greater than 0
+        }
+    }
+
+    case loop_cond_with_index {
+        given {
+            v = vec![0, 1, 2],
+        }
+        check {
+            for each n at index i in &v, *n equal i
+        }
+    }
+
+    case sorted_collection {
+        given {
+            v = vec![1, 2, 2, 5],
+            words = ["ccc", "bb", "a"],
+        }
+        check {
+            v is sorted and &words is sorted by |w: &&str| w.len() descending
+        }
+    }
+
+    case unique_collection {
+        given {
+            v = vec![3, 1, 2],
+        }
+        check {
+            v has unique elements and v not equal elementwise [1, 2, 3]
+        }
+    }
 
+    case consecutive_pairs {
+        given {
+            v = vec![1, 3, 6, 10],
+        }
+        check {
+            for each consecutive (a, b) at index i in &v, *a less than *b and i less than 3
+        }
+    }
+
+    case elementwise_equality {
+        given {
+            v = vec![1, 2, 3],
+            arr = [1, 2, 3],
+        }
+        check {
+            v equal elementwise arr
+        }
+    }
+
+    case unordered_equality {
+        given {
+            v = vec![3, 1, 2, 1],
+            map = std::collections::HashMap::from([(1, "a"), (2, "b")]),
+        }
+        check {
+            &v equal ignoring order &[1, 1, 2, 3]
+            and &v not equal ignoring order &[1, 2, 3]
+            and map.keys().copied() equal ignoring order [2, 1]
+        }
+    }
+
+    case unordered_equality_of_owned_vecs {
+        given {
+            v = vec![String::from("b"), String::from("a")],
+            w = vec![String::from("a"), String::from("b")],
+            x = vec![String::from("a"), String::from("a")],
+        }
+        check {
+            v equal ignoring order w and x same elements as vec![String::from("a")]
+        }
+    }
+
+    case unordered_equality_of_method_calls {
+        given {
+            v = vec![3, 1, 2],
+            w = [1, 2, 3],
+        }
+        check {
+            v same elements as w.iter().copied()
+            and w.iter().map(|x| x * 2) equal ignoring order [2, 4, 6]
+        }
+    }
+
+    case same_elements {
+        given {
+            v = vec![3, 1, 2, 1],
+        }
+        check {
+            &v same elements as &[1, 2, 3] and &v not same elements as &[1, 2]
+        }
+    }
+
+    mod field_tests {
+        #[derive(Debug, PartialEq)]
+        #[allow(dead_code)]
+        pub enum State {
+            Pending,
+            Done,
+        }
+        #[derive(Debug)]
+        pub struct Item {
+            pub id: u32,
+            pub name: String,
+            pub state: State,
+        }
+    }
+
+    case fields_of_struct {
+        given {
+            item = field_tests::Item { id: 1, name: "apple".into(), state: field_tests::State::Done },
+            expected_id = 1,
+        }
+        check {
+            item has fields {
+                id equal expected_id,
+                name starts with "a",
+                name.len() greater than 3,
+                state matches field_tests::State::Done,
+            }
+            and item.name not starts with "b"
+        }
+    }
+
+    case loop_cond_with_pattern_and_index {
+        given {
+            v = vec![(0, 'a'), (1, 'b')],
+        }
+        check {
+            for any (n, _) at index i in &v, *n equal i and i greater than 0
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "v is sorted where, v=[1, 3, 2], first violating index=Some(2)")]
+fn sorted_failure_points_at_violation() {
+    let v = vec![1, 3, 2];
+    check!(v is sorted);
+}
+
+#[test]
+#[should_panic(
+    expected = "v has unique elements where, v=[1, 2, 1], first violating index=Some(2)"
+)]
+fn unique_elements_failure_points_at_duplicate() {
+    let v = vec![1, 2, 1];
+    check!(v has unique elements);
+}
+
+#[test]
+#[should_panic(
+    expected = "for each consecutive (a, b) in & v, * a less than * b where, & v=[1, 3, 2]"
+)]
+fn consecutive_pairs_failure_shows_collection() {
+    let v = vec![1, 3, 2];
+    check!(for each consecutive (a, b) in &v, *a less than *b);
+}
+
+#[test]
+#[should_panic(
+    expected = "v equal elementwise w where, v=[1, 2, 3], w=[1, 5, 3], first mismatch index=Some(1)"
+)]
+fn elementwise_failure_points_at_mismatch() {
+    let (v, w) = (vec![1, 2, 3], [1, 5, 3]);
+    check!(v equal elementwise w);
+}
+
+#[test]
+#[should_panic(expected = "v equal ignoring order w where, missing=[3], extra=[2]")]
+fn unordered_failure_shows_missing_and_extra() {
+    let (v, w) = (vec![1, 2, 2], vec![2, 1, 3]);
+    check!(v equal ignoring order w);
+}
+
+#[test]
+#[should_panic(expected = "v same elements as [1, 4] where, missing=[4], extra=[2]")]
+fn same_elements_failure_shows_missing_and_extra() {
+    let v = vec![1, 2, 1];
+    check!(v same elements as [1, 4]);
+}
 
+#[test]
+#[should_panic(expected = "v equal ignoring order counted() where, missing=[3], extra=[2]")]
+fn unordered_failure_evaluates_operands_once() {
+    let (v, w) = (vec![1, 2, 2], vec![2, 1, 3]);
+    let calls = std::cell::Cell::new(0);
+    let counted = || {
+        calls.set(calls.get() + 1);
+        assert_eq!(calls.get(), 1, "operand evaluated again");
+        w.iter().copied()
+    };
+    check!(v equal ignoring order counted());
 }
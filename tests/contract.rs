@@ -0,0 +1,63 @@
+use rusty_check::{ensure, require};
+use rusty_check_runtime::RustyCheckViolation;
+
+fn withdraw(balance: u32, amount: u32) -> Result<u32, RustyCheckViolation> {
+    require!(amount equal or less than balance);
+    let rest = balance - amount;
+    ensure!(rest less than balance or amount equal 0);
+    Ok(rest)
+}
+
+#[derive(Debug)]
+enum AccountError {
+    Violation(RustyCheckViolation),
+}
+
+impl From<RustyCheckViolation> for AccountError {
+    fn from(violation: RustyCheckViolation) -> Self {
+        AccountError::Violation(violation)
+    }
+}
+
+fn deposit(balance: u32, amount: u32) -> Result<u32, AccountError> {
+    require!(amount greater than 0);
+    Ok(balance + amount)
+}
+
+fn strict_deposit(balance: u32, amount: u32) -> u32 {
+    require!(cfg { violation = panic }, amount greater than 0);
+    balance + amount
+}
+
+#[test]
+fn contract_holds() {
+    assert_eq!(withdraw(10, 3).unwrap(), 7);
+    assert_eq!(deposit(10, 3).unwrap(), 13);
+    assert_eq!(strict_deposit(10, 3), 13);
+}
+
+#[test]
+fn violated_precondition_returns_error() {
+    let violation = withdraw(3, 10).unwrap_err();
+    assert_eq!(
+        violation.message,
+        "Precondition failed: amount equal or less than balance"
+    );
+    assert_eq!(
+        violation.values,
+        vec![
+            ("amount".to_owned(), "10".to_owned()),
+            ("balance".to_owned(), "3".to_owned()),
+        ]
+    );
+    assert!(matches!(
+        deposit(1, 0),
+        Err(AccountError::Violation(RustyCheckViolation { .. }))
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Precondition failed: amount greater than 0 where, amount=0")]
+fn violated_precondition_panics() {
+    strict_deposit(1, 0);
+}
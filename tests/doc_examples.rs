@@ -0,0 +1,33 @@
+use std::{path::Path, process::Command};
+
+/// Runs the doctests of a crate using `doc for` clauses, so the generated
+/// examples are compiled and run by `rustdoc` like any other doctest.
+#[test]
+fn doc_examples_run_as_doctests() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    // The dependencies are already fetched for the tests of this crate.
+    let output = Command::new(cargo)
+        .args(["test", "--doc", "--offline", "--manifest-path"])
+        .arg(root.join("tests/fixtures/doc_examples/Cargo.toml"))
+        .env(
+            "CARGO_TARGET_DIR",
+            root.join("target/rusty_check/doc_examples"),
+        )
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("__rusty_doc_checks_addition"), "{}", stdout);
+    assert!(
+        !stdout.contains("__rusty_doc_checks_no_example"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("test result: ok. 1 passed"), "{}", stdout);
+}
@@ -0,0 +1,37 @@
+pub mod math {
+    pub fn add(a: u32, b: u32) -> u32 {
+        a + b
+    }
+}
+
+use rusty_check::rusty_check;
+
+rusty_check! {
+    global {
+        cfg {
+            module name = checks,
+        }
+    }
+
+    use super::math::add;
+
+    case addition {
+        doc for crate::math::add
+        given {
+            a = 2,
+            b = 3
+        }
+        do {
+            let sum = add(a, b);
+        }
+        check {
+            sum equal 5
+        }
+    }
+
+    case no_example {
+        check {
+            add(1, 1) equal 2
+        }
+    }
+}
@@ -3,13 +3,13 @@ rusty_check! {
     global {
         cfg {
             comment = simple,
-            create module = false
+            create module = false,
         }
         vars {
-            SOME_VAL: u32 = 21
+            SOME_VAL: u32 = 21,
         }
         consts {
-            MY_CONST: u32 = 10
+            MY_CONST: u32 = 10,
         }
     }
 
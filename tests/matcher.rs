@@ -0,0 +1,57 @@
+use rusty_check::rusty_check;
+rusty_check! {
+    use rusty_check_runtime::Matcher;
+
+    struct ValidEmail;
+
+    impl Matcher<&str> for ValidEmail {
+        fn matches(&self, actual: &&str) -> bool {
+            actual.contains('@')
+        }
+        fn describe_mismatch(&self, actual: &&str) -> String {
+            format!("{:?} has no `@`", actual)
+        }
+    }
+
+    fn is_valid_email() -> ValidEmail {
+        ValidEmail
+    }
+
+    struct DivisibleBy(u32);
+
+    impl Matcher<u32> for DivisibleBy {
+        fn matches(&self, actual: &u32) -> bool {
+            actual.is_multiple_of(self.0)
+        }
+        fn describe_mismatch(&self, actual: &u32) -> String {
+            format!("{} leaves remainder {}", actual, actual % self.0)
+        }
+    }
+
+    case satisfies_matcher {
+        given {
+            email = "user@example.com",
+        }
+        check {
+            email satisfies is_valid_email()
+        }
+    }
+
+    case not_satisfies_matcher {
+        given {
+            email = "user.example.com",
+        }
+        check {
+            email not satisfies is_valid_email()
+        }
+    }
+
+    case satisfies_matcher_with_arguments {
+        given {
+            n = 12,
+        }
+        check {
+            n satisfies DivisibleBy(3) and n not satisfies DivisibleBy(5)
+        }
+    }
+}
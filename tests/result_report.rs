@@ -0,0 +1,59 @@
+use rusty_check::rusty_check;
+
+// The cases are declared inside the test, so the harness does not run them on its own
+// and only this test appends to its records file.
+#[test]
+#[allow(unnameable_test_items)]
+fn results_are_recorded_and_collected() {
+    rusty_check! {
+        global {
+            cfg {
+                result report = "target/rusty_check/test_result_report/results.jsonl",
+                create module = false,
+                comment = simple,
+            }
+        }
+
+        case passing_case {
+            given {
+                a = 1,
+            }
+            check {
+                a equal 1
+            }
+        }
+
+        case unstable_case {
+            cfg {
+                unstable = true,
+            }
+            check {
+                1 equal 2
+            }
+        }
+    }
+
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target/rusty_check/test_result_report/results.jsonl");
+    _ = std::fs::remove_file(&path);
+    passing_case();
+    unstable_case();
+    let (junit, _, results) = rusty_check_runtime::write_reports(&path).unwrap();
+    assert_eq!(results.len(), 2);
+    let result = |case: &str| results.iter().find(|result| result.case == case).unwrap();
+    assert_eq!(
+        result("passing_case").status,
+        rusty_check_runtime::CaseStatus::Passed
+    );
+    assert_eq!(
+        result("unstable_case").status,
+        rusty_check_runtime::CaseStatus::Unstable
+    );
+    assert_eq!(
+        result("unstable_case").comment.as_deref(),
+        Some("1 equal 2")
+    );
+    assert_eq!(result("unstable_case").module, "result_report");
+    let junit = std::fs::read_to_string(junit).unwrap();
+    assert!(junit.contains("<testcase name=\"passing_case\" classname=\"result_report\""));
+}
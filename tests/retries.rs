@@ -0,0 +1,55 @@
+use rusty_check::rusty_check;
+rusty_check! {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::{thread::sleep, time::Duration};
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    static STRICT_CALLS: AtomicU32 = AtomicU32::new(0);
+
+    static HANGING_CALLS: AtomicU32 = AtomicU32::new(0);
+
+    /// Succeeds on every third call.
+    fn flaky(calls: &AtomicU32) -> bool {
+        calls.fetch_add(1, Ordering::SeqCst) % 3 == 2
+    }
+
+    /// Hangs on the first call and returns at once on every next one.
+    fn hangs_once(calls: &AtomicU32) -> bool {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            sleep(Duration::from_secs(60));
+        }
+        true
+    }
+
+    case passes_after_retries {
+        cfg {
+            retries = 2,
+            backoff = 1ms,
+        }
+        check {
+            flaky(&CALLS) equal true
+        }
+    }
+
+    case passes_with_min_pass_rate {
+        cfg {
+            retries = 5,
+            min pass rate = 0.3,
+        }
+        check {
+            flaky(&STRICT_CALLS) equal true
+        }
+    }
+
+    case passes_after_timed_out_attempt {
+        cfg {
+            retries = 2,
+            backoff = 10ms,
+            timeout = 200ms,
+        }
+        check {
+            hangs_once(&HANGING_CALLS) equal true
+        }
+    }
+}
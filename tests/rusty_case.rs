@@ -0,0 +1,59 @@
+use rusty_check::rusty_case;
+
+fn double(value: u32) -> u32 {
+    value * 2
+}
+
+/// Cases written as plain functions, next to ordinary tests.
+#[rusty_case]
+fn doubles_value() {
+    given! {
+        value = 21
+    }
+    let doubled = double(value);
+    check! {
+        doubled equal 42
+    }
+}
+
+#[rusty_case(comment = simple, tags = [math])]
+fn check_only() {
+    check! {
+        double(2) equal 4 and double(0) equal 0
+    }
+}
+
+#[rusty_case]
+fn paren_macros() {
+    given!(value = 2);
+    check!(double(value) equal 4)
+}
+
+#[rusty_case(unstable = true)]
+fn unstable_case() {
+    given! {
+        v = vec![1, 2, 3]
+    }
+    check! {
+        for each x in &v, *x less than 3
+    }
+}
+
+/// Keeps its doc comment and visibility on the generated test.
+#[rusty_case]
+pub fn documented_public_case() {
+    check! {
+        double(1) equal 2
+    }
+}
+
+mod nested {
+    use super::*;
+
+    #[rusty_case(timeout = 1s)]
+    fn uses_parent_items() {
+        check! {
+            double(5) equal 10
+        }
+    }
+}
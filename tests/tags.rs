@@ -0,0 +1,36 @@
+use rusty_check::rusty_check;
+rusty_check! {
+    global {
+        cfg {
+            tags = [db],
+        }
+        consts {
+            ROWS: usize = 3,
+        }
+    }
+
+    fn query(rows: usize) -> Vec<usize> {
+        (0..rows).collect()
+    }
+
+    case query_returns_rows {
+        given {
+            rows = query(ROWS),
+        }
+        check {
+            rows.len() equal ROWS
+        }
+    }
+
+    case slow_query_returns_rows {
+        cfg {
+            tags = [slow, network],
+        }
+        given {
+            rows = query(ROWS * 1000),
+        }
+        check {
+            rows.len() equal ROWS * 1000
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use rusty_check::rusty_check;
+rusty_check! {
+    use std::{thread::sleep, time::Duration};
+
+    fn slow_double(value: u32) -> u32 {
+        sleep(Duration::from_millis(10));
+        value * 2
+    }
+
+    case finishes_within_timeout {
+        cfg {
+            timeout = 2s,
+        }
+        given {
+            value = 21,
+        }
+        do {
+            let doubled = slow_double(value);
+        }
+        check {
+            doubled equal 42
+        }
+    }
+}
+
+// The hanging case is declared inside the test, so the harness does not run it on its own.
+#[test]
+#[allow(unnameable_test_items)]
+fn timed_out_case_fails_without_blocking() {
+    use std::time::{Duration, Instant};
+
+    rusty_check! {
+        global {
+            cfg {
+                create module = false,
+            }
+        }
+
+        case hangs {
+            cfg {
+                timeout = 50ms,
+            }
+            given {
+                done = true,
+            }
+            do {
+                std::thread::sleep(std::time::Duration::from_secs(60));
+            }
+            check {
+                done equal true
+            }
+        }
+    }
+
+    let start = Instant::now();
+    let panic = std::panic::catch_unwind(hangs).unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(10));
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("Case `hangs` timed out after"));
+    assert!(message.ends_with("the timeout is 50ms"));
+}
@@ -0,0 +1,30 @@
+use rusty_check::rusty_check;
+rusty_check! {
+    global {
+        cfg {
+            unstable report = "target/rusty_check/test_unstable_report.jsonl",
+        }
+    }
+
+    #[test]
+    fn failed_unstable_check_is_recorded() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("target/rusty_check/test_unstable_report.jsonl");
+        _ = std::fs::remove_file(&path);
+        unstable_case_fails();
+        let report = std::fs::read_to_string(&path).unwrap();
+        assert!(report.lines().any(|line| line.starts_with(
+            r#"{"case":"unstable_case_fails","module":"unstable_report::tests","comment":"1 equal 2","#
+        )));
+    }
+
+    case unstable_case_fails {
+        cfg {
+            unstable = true,
+            comment = simple,
+        }
+        check {
+            1 equal 2
+        }
+    }
+}